@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use league_helper::config::Config;
+use league_helper::models::flash_slot::FlashSlot;
+use league_helper::models::ugg::position::Position;
+use league_helper::models::ugg::queue::Queue;
+use league_helper::models::ugg::rank_tier::RankTier;
+use league_helper::models::ugg::region::Region;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub config_args: ConfigArgs,
+    /// Show debug output on the console.
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
+    pub verbose: bool,
+    /// Only show warnings and errors on the console.
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+}
+
+#[derive(Debug, Default, Subcommand)]
+pub enum Command {
+    /// Save item sets and apply runes and summoner spells during champ select (default).
+    #[default]
+    Run,
+    /// Download the latest U.GG build data into the cache.
+    Update,
+    /// Write the cached builds as item sets into the League install directory.
+    ExportItemSets,
+    /// Remove the item sets and rune pages created by league_helper.
+    Clean,
+    /// Print the cached build for a champion.
+    Show {
+        /// Champion name or id (e.g. "Lee Sin" or LeeSin).
+        champion: String,
+        /// Position to show, defaults to every position we have data for.
+        position: Option<Position>,
+        /// Queue to show the build for.
+        #[arg(long, default_value_t = Queue::RankedSolo)]
+        queue: Queue,
+    },
+    /// Inspect the local build data cache.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// List the cached build data files.
+    Info,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ConfigArgs {
+    /// Path to the config file. Defaults to league_helper/config.toml in the platform config directory.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Write the config including any overrides given on the command line back to the config file.
+    #[arg(long, global = true)]
+    pub save_config: bool,
+    /// Region to use U.GG statistics from (e.g. EUW, NA, KR, World). Saved to the config file.
+    #[arg(long, global = true)]
+    pub region: Option<Region>,
+    /// Rank tier to use U.GG statistics from (e.g. emerald_plus, master_plus). Saved to the config file.
+    #[arg(long, global = true)]
+    pub rank_tier: Option<RankTier>,
+    /// How often to poll the League client in milliseconds.
+    #[arg(long, global = true)]
+    pub poll_interval_ms: Option<u64>,
+    /// Prefix used to name the rune pages and item sets we create.
+    #[arg(long, global = true)]
+    pub page_prefix: Option<String>,
+    /// Maximum age of the U.GG cache before it is downloaded again.
+    #[arg(long, global = true)]
+    pub cache_max_age_hours: Option<u64>,
+    /// Position to start champ select with (e.g. Jungle, Mid).
+    #[arg(long, global = true)]
+    pub position: Option<Position>,
+    /// Directory to store downloaded data in.
+    #[arg(long, global = true)]
+    pub data_dir: Option<PathBuf>,
+    /// Only use the newest build data already on disk, without contacting DDragon or U.GG.
+    #[arg(long, global = true)]
+    pub offline: bool,
+    /// Language for champion and rune names (e.g. ko_KR, fr_FR). Defaults to the client's.
+    #[arg(long, global = true)]
+    pub locale: Option<String>,
+    /// Summoner spell key to put Flash on (D or F).
+    #[arg(long, global = true)]
+    pub flash_slot: Option<FlashSlot>,
+    /// Name of a rune page that may be overwritten when every rune page slot is taken.
+    #[arg(long, global = true)]
+    pub scratch_page: Option<String>,
+    /// Log the rune pages that would be created, overwritten or deleted without changing them.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+}
+
+impl ConfigArgs {
+    /// Applies the overrides that are saved to the config file even without
+    /// `--save-config`, returning whether there were any.
+    pub fn apply_persistent(&self, config: &mut Config) -> bool {
+        if let Some(region) = self.region {
+            config.region = region;
+        }
+
+        if let Some(rank_tier) = self.rank_tier {
+            config.rank_tier = rank_tier;
+        }
+
+        self.region.is_some() || self.rank_tier.is_some()
+    }
+
+    /// Overrides the values from the config file with any given on the command line.
+    pub fn apply(&self, config: &mut Config) {
+        self.apply_persistent(config);
+
+        if let Some(poll_interval_ms) = self.poll_interval_ms {
+            config.poll_interval_ms = poll_interval_ms;
+        }
+
+        if let Some(page_prefix) = &self.page_prefix {
+            config.page_prefix = page_prefix.clone();
+        }
+
+        if let Some(cache_max_age_hours) = self.cache_max_age_hours {
+            config.cache_max_age_hours = cache_max_age_hours;
+        }
+
+        if let Some(position) = self.position {
+            config.default_position = position;
+        }
+
+        if let Some(data_dir) = &self.data_dir {
+            config.data_dir = data_dir.clone();
+        }
+
+        if self.offline {
+            config.offline = true;
+        }
+
+        if let Some(locale) = &self.locale {
+            config.locale = Some(locale.clone());
+        }
+
+        if let Some(flash_slot) = self.flash_slot {
+            config.flash_slot = Some(flash_slot);
+        }
+
+        if let Some(scratch_page) = &self.scratch_page {
+            config.scratch_page = Some(scratch_page.clone());
+        }
+
+        if self.dry_run {
+            config.dry_run = true;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use app_error::Result;
+use league_helper::config::Config;
+use league_helper::models::ugg::cache_file::CacheFile;
+
+pub fn info(config: &Config) -> Result<()> {
+    let cache_files = CacheFile::list(&config.data_dir)?;
+
+    println!("Cache directory: {}", config.data_dir.display());
+
+    if cache_files.is_empty() {
+        println!("No cached build data found.");
+
+        return Ok(());
+    }
+
+    for cache_file in cache_files {
+        let age_hours = cache_file
+            .modified
+            .elapsed()
+            .map(|age| age.as_secs() / 3600)
+            .unwrap_or(0);
+
+        let active = cache_file.region == config.region && cache_file.rank_tier == config.rank_tier;
+
+        println!(
+            "{} patch {} {} {} - {:.1} MB, {} hours old{}",
+            cache_file.path.display(),
+            cache_file.patch_version,
+            cache_file.region,
+            cache_file.rank_tier,
+            cache_file.size as f64 / (1024.0 * 1024.0),
+            age_hours,
+            if active { " (active)" } else { "" }
+        );
+    }
+
+    Ok(())
+}
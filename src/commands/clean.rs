@@ -0,0 +1,43 @@
+use app_error::Result;
+use lcu_driver::LcuDriver;
+use league_helper::config::Config;
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+use tracing::info;
+
+use crate::commands::export_item_sets::item_builds_path;
+
+pub async fn run(config: &Config) -> Result<()> {
+    let lcu_driver = LcuDriver::connect_wait().await;
+
+    let builds_path = item_builds_path(&lcu_driver).await?;
+
+    if config.dry_run {
+        info!(
+            "Dry run: would delete the item sets in {}",
+            builds_path.display()
+        );
+    } else {
+        UggBuildData::delete_old_item_builds(&builds_path)?;
+    }
+
+    let pages_to_delete = lcu_driver
+        .get_perks_pages()
+        .await?
+        .pages
+        .into_iter()
+        .filter(|p| p.name.starts_with(&config.page_prefix) && p.is_deletable);
+
+    for page in pages_to_delete {
+        if config.dry_run {
+            info!("Dry run: would delete rune page: {}", page.name);
+
+            continue;
+        }
+
+        info!("Deleting rune page: {}", page.name);
+
+        lcu_driver.delete_perks_page(page.id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+
+use app_error::{bail, AppErrorExt, Result};
+use lcu_driver::{Initialized, LcuDriver};
+use league_helper::config::Config;
+use league_helper::models::localization::Localization;
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+use tracing::info;
+
+use crate::commands::{load_build_data, load_localization};
+
+pub async fn run(config: &Config) -> Result<()> {
+    let ugg_build_data = load_build_data(config).await?;
+
+    let lcu_driver = LcuDriver::connect_wait().await;
+
+    let localization = load_localization(config, Some(&lcu_driver)).await;
+
+    export_item_sets(&lcu_driver, &ugg_build_data, config, &localization).await
+}
+
+pub async fn item_builds_path(lcu_driver: &LcuDriver<Initialized>) -> Result<PathBuf> {
+    let builds_path = lcu_driver.league_install_dir().await.join("Config");
+
+    if !builds_path.exists() {
+        bail!("builds path does not exist");
+    }
+
+    let builds_path = builds_path.join("Champions");
+
+    if !builds_path.exists() {
+        fs::create_dir(&builds_path).context("failed to create 'Champions' builds path")?;
+    }
+
+    Ok(builds_path)
+}
+
+pub async fn export_item_sets(
+    lcu_driver: &LcuDriver<Initialized>,
+    ugg_build_data: &UggBuildData,
+    config: &Config,
+    localization: &Localization,
+) -> Result<()> {
+    let builds_path = item_builds_path(lcu_driver).await?;
+
+    if config.dry_run {
+        info!(
+            "Dry run: would replace the item sets in {}",
+            builds_path.display()
+        );
+
+        return Ok(());
+    }
+
+    UggBuildData::delete_old_item_builds(&builds_path)
+        .context("failed to delete old item builds")?;
+
+    ugg_build_data
+        .save_item_builds(&builds_path, &config.page_prefix, localization)
+        .context("failed to save item builds")?;
+
+    Ok(())
+}
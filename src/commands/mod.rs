@@ -0,0 +1,110 @@
+use app_error::Result;
+use lcu_driver::{Initialized, LcuDriver};
+use league_helper::config::Config;
+use league_helper::models::ddragon_summoner_spells::SummonerSpellCatalogue;
+use league_helper::models::ddragon_updater::DDragonUpdater;
+use league_helper::models::localization::{is_valid_locale, Localization, DEFAULT_LOCALE};
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+use tracing::{info, warn};
+
+pub mod cache;
+pub mod clean;
+pub mod export_item_sets;
+pub mod run;
+pub mod show;
+pub mod update;
+
+/// Loads the build data for the current patch, falling back to the newest cache
+/// on disk when offline or when the build data can't be downloaded.
+pub async fn load_build_data(config: &Config) -> Result<UggBuildData> {
+    if config.offline {
+        return UggBuildData::load_offline(config).await;
+    }
+
+    // DDragon versions are cached too, so this can succeed offline and fail in U.GG later
+    let loaded = match DDragonUpdater::new(config).await {
+        Ok(ddragon) => UggBuildData::load(&ddragon, config).await,
+        Err(e) => Err(e),
+    };
+
+    match loaded {
+        Ok(ugg_build_data) => Ok(ugg_build_data),
+        Err(e) => {
+            warn!(
+                "Failed to load build data ({}), switching to offline mode",
+                e
+            );
+
+            UggBuildData::load_offline(config).await
+        }
+    }
+}
+
+/// Champion and rune names in the configured locale, or the client's if none is set.
+/// Falls back to the English names stored with the builds if they can't be downloaded.
+pub async fn load_localization(
+    config: &Config,
+    lcu_driver: Option<&LcuDriver<Initialized>>,
+) -> Localization {
+    let locale = match (&config.locale, lcu_driver) {
+        (Some(locale), _) => locale.clone(),
+        (None, Some(lcu_driver)) => match lcu_driver.get_region_locale().await {
+            Ok(region_locale) if is_valid_locale(&region_locale.locale) => region_locale.locale,
+            Ok(region_locale) => {
+                warn!("Unknown client locale: {}", region_locale.locale);
+
+                DEFAULT_LOCALE.to_owned()
+            }
+            Err(e) => {
+                warn!("Failed to get the client locale: {}", e);
+
+                DEFAULT_LOCALE.to_owned()
+            }
+        },
+        (None, None) => DEFAULT_LOCALE.to_owned(),
+    };
+
+    if config.offline {
+        return Localization::default();
+    }
+
+    let localization = match DDragonUpdater::new(config).await {
+        Ok(ddragon) => Localization::load(&ddragon, &locale).await,
+        Err(e) => Err(e),
+    };
+
+    match localization {
+        Ok(localization) => {
+            info!("Using {} names", localization.locale);
+
+            localization
+        }
+        Err(e) => {
+            warn!("Failed to load {} names, using English: {}", locale, e);
+
+            Localization::default()
+        }
+    }
+}
+
+/// The summoner spell catalogue used to check which spells a game mode allows, or
+/// `None` when offline or if it can't be downloaded.
+pub async fn load_summoner_spells(config: &Config) -> Option<SummonerSpellCatalogue> {
+    if config.offline {
+        return None;
+    }
+
+    let summoner_spells = match DDragonUpdater::new(config).await {
+        Ok(ddragon) => ddragon.download_latest_summoner_spells().await,
+        Err(e) => Err(e),
+    };
+
+    match summoner_spells {
+        Ok(summoner_spells) => Some(summoner_spells),
+        Err(e) => {
+            warn!("Failed to load the summoner spell catalogue: {}", e);
+
+            None
+        }
+    }
+}
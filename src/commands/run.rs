@@ -0,0 +1,815 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use app_error::{AppErrorExt, Result};
+use lcu_driver::endpoints::champ_select::{ChampSelectSession, MySelection};
+use lcu_driver::endpoints::gameflow::{GameFlowSession, GameMode};
+use lcu_driver::endpoints::perks::PerksPage;
+use lcu_driver::endpoints::summoner::Summoner;
+use lcu_driver::{Initialized, LcuDriver};
+use league_helper::config::Config;
+use league_helper::models::ddragon_summoner_spells::SummonerSpellCatalogue;
+use league_helper::models::flash_slot::{FlashSlot, FlashSlotMemory};
+use league_helper::models::localization::Localization;
+use league_helper::models::rune_page_plan::{RunePagePlan, RunePageSlot};
+use league_helper::models::ugg::lazy_ugg_client::LazyUggClient;
+use league_helper::models::ugg::position::Position;
+use league_helper::models::ugg::queue::Queue;
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+use league_helper::models::ugg::ugg_matchups::UggMatchups;
+use league_helper::session::{Session, SessionState, Transition};
+use tokio::sync::{Notify, RwLock};
+use tracing::{debug, error, info, info_span, warn, Instrument, Span};
+
+use crate::commands::export_item_sets::export_item_sets;
+use crate::commands::{load_build_data, load_localization, load_summoner_spells};
+use crate::lcu_events::{LcuEvents, LcuUpdate};
+
+// Polls in a row that can fail before we assume the client has closed
+const MAX_FAILED_POLLS: usize = 3;
+
+#[derive(Debug)]
+struct HotkeySelection {
+    position: Position,
+    // Set once the position has been changed manually with the up/down arrow keys
+    overridden: bool,
+    // Which of the build's rune pages to use, cycled with the right arrow key
+    rune_page: usize,
+    // The arrow keys only change the selection during champ select
+    active: bool,
+}
+
+impl HotkeySelection {
+    fn new(position: Position) -> Self {
+        Self {
+            position,
+            overridden: false,
+            rune_page: 0,
+            active: false,
+        }
+    }
+}
+
+pub async fn run(config: &Config) -> Result<()> {
+    let ugg_build_data = load_build_data(config).await?;
+    let summoner_spells = load_summoner_spells(config).await;
+
+    let flash_slots = FlashSlotMemory::load(config).await.unwrap_or_else(|e| {
+        warn!("Failed to load the remembered Flash slots: {}", e);
+
+        FlashSlotMemory::default()
+    });
+
+    let hotkeys = Arc::new(RwLock::new(HotkeySelection::new(config.default_position)));
+    let hotkey_pressed = Arc::new(Notify::new());
+    let builds_loaded = Arc::new(Notify::new());
+    let ugg_client = Arc::new(LazyUggClient::new(config));
+
+    listen_for_hotkeys(hotkeys.clone(), hotkey_pressed.clone());
+
+    let mut exported_item_sets = None;
+
+    let (lcu_driver, my_summoner, localization) =
+        connect_to_client(&ugg_build_data, config, &mut exported_item_sets).await?;
+
+    let mut handler = ChampSelectHandler {
+        lcu_driver,
+        ugg_build_data: &ugg_build_data,
+        config,
+        my_summoner,
+        localization,
+        summoner_spells,
+        flash_slots,
+        session: Session::new(),
+        hotkeys,
+        hotkey_pressed,
+        builds_loaded: builds_loaded.clone(),
+        game_flow_session: None,
+        matchups: UggMatchups::new(
+            config,
+            &ugg_build_data.patch_version,
+            ugg_client,
+            builds_loaded,
+        ),
+        previous_selection: None,
+        applied_spells: None,
+        champ_select_count: 0,
+        champ_select_span: Span::none(),
+    };
+
+    loop {
+        handler.listen().await;
+
+        warn!("Lost connection to the League client, waiting for it to restart...");
+
+        if let Some(transition) = handler.session.reset() {
+            handler.on_transition(transition).await;
+        }
+
+        // The client gets a new port and auth token every time it starts
+        let (lcu_driver, my_summoner, localization) =
+            connect_to_client(&ugg_build_data, config, &mut exported_item_sets).await?;
+
+        handler.lcu_driver = lcu_driver;
+        handler.my_summoner = my_summoner;
+        handler.localization = localization;
+    }
+}
+
+/// Waits for the League client and the logged in summoner, exporting the item sets
+/// whenever the client is running from a different install directory or language.
+async fn connect_to_client(
+    ugg_build_data: &UggBuildData,
+    config: &Config,
+    exported_item_sets: &mut Option<(PathBuf, String)>,
+) -> Result<(LcuDriver<Initialized>, Summoner, Localization)> {
+    loop {
+        let lcu_driver = LcuDriver::connect_wait().await;
+
+        let localization = load_localization(config, Some(&lcu_driver)).await;
+
+        let exported = (
+            lcu_driver.league_install_dir().await,
+            localization.locale.clone(),
+        );
+
+        if exported_item_sets.as_ref() != Some(&exported) {
+            // Not worth stopping for, the runes and summoner spells still work without them
+            match export_item_sets(&lcu_driver, ugg_build_data, config, &localization).await {
+                Ok(()) => *exported_item_sets = Some(exported),
+                Err(e) => warn!("Failed to export item sets: {}", e),
+            }
+        }
+
+        match lcu_driver.get_current_summoner().await {
+            Ok(my_summoner) => {
+                info!("Connected to the League client");
+
+                return Ok((lcu_driver, my_summoner, localization));
+            }
+            Err(e) => {
+                warn!("Failed to get current summoner, retrying: {}", e);
+
+                tokio::time::sleep(config.poll_interval()).await;
+            }
+        }
+    }
+}
+
+fn listen_for_hotkeys(hotkeys: Arc<RwLock<HotkeySelection>>, hotkey_pressed: Arc<Notify>) {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = rdev::listen(move |event| {
+            let rdev::EventType::KeyPress(
+                key @ (rdev::Key::UpArrow | rdev::Key::DownArrow | rdev::Key::RightArrow),
+            ) = event.event_type
+            else {
+                return;
+            };
+
+            let mut selection = hotkeys.blocking_write();
+
+            if !selection.active {
+                return;
+            }
+
+            match key {
+                rdev::Key::UpArrow | rdev::Key::DownArrow => {
+                    let changed = if key == rdev::Key::UpArrow {
+                        selection.position.previous()
+                    } else {
+                        selection.position.next()
+                    };
+
+                    if changed {
+                        selection.overridden = true;
+                        info!("Position set to: {}", selection.position);
+                        hotkey_pressed.notify_one();
+                    }
+                }
+                _ => {
+                    selection.rune_page = selection.rune_page.wrapping_add(1);
+                    info!("Switching to the next rune page");
+                    hotkey_pressed.notify_one();
+                }
+            }
+        }) {
+            error!("rdev listen error: {:?}", e);
+        }
+    });
+}
+
+// What the last rune page and summoner spells were set for
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct AppliedSelection {
+    champion_id: isize,
+    position: Position,
+    rune_page: usize,
+    opponent_id: Option<isize>,
+    // Change once builds have been fetched, so they get applied
+    has_matchup_build: bool,
+}
+
+// Updates already queued when we set the spells still show the old ones, so a change only
+// counts as the player's once an update has shown ours
+#[derive(Clone, Copy, Debug)]
+struct AppliedSpells {
+    spells: (isize, isize),
+    confirmed: bool,
+}
+
+struct ChampSelectHandler<'a> {
+    lcu_driver: LcuDriver<Initialized>,
+    ugg_build_data: &'a UggBuildData,
+    config: &'a Config,
+    my_summoner: Summoner,
+    localization: Localization,
+    summoner_spells: Option<SummonerSpellCatalogue>,
+    flash_slots: FlashSlotMemory,
+    session: Session,
+    hotkeys: Arc<RwLock<HotkeySelection>>,
+    // Lets a hotkey apply straight away instead of on the next client update
+    hotkey_pressed: Arc<Notify>,
+    // Lets a build fetched in the background replace the one applied without it
+    builds_loaded: Arc<Notify>,
+    game_flow_session: Option<GameFlowSession>,
+    matchups: UggMatchups,
+    previous_selection: Option<AppliedSelection>,
+    // The summoner spells we last set, to notice the player moving Flash themselves
+    applied_spells: Option<AppliedSpells>,
+    champ_select_count: usize,
+    champ_select_span: Span,
+}
+
+impl ChampSelectHandler<'_> {
+    /// Handles client updates until the connection to the client is lost.
+    async fn listen(&mut self) {
+        let mut lcu_events = None;
+        let mut failed_polls = 0;
+
+        while failed_polls < MAX_FAILED_POLLS {
+            // Retry the websocket on every poll, the client may just be restarting it
+            if lcu_events.is_none() {
+                match LcuEvents::connect(&self.lcu_driver).await {
+                    Ok(events) => {
+                        info!("Listening for League client events");
+
+                        lcu_events = Some(events);
+                    }
+                    Err(e) => warn!(
+                        "Failed to connect to the League client websocket, polling instead: {}",
+                        e
+                    ),
+                }
+
+                // Catch up on anything that happened while we weren't subscribed
+                match self.poll().await {
+                    Ok(()) => failed_polls = 0,
+                    Err(_) => failed_polls += 1,
+                }
+
+                if lcu_events.is_none() {
+                    tokio::time::sleep(self.config.poll_interval()).await;
+                }
+            }
+
+            if let Some(events) = &mut lcu_events {
+                tokio::select! {
+                    update = events.next() => match update {
+                        Some(Ok(update)) => self.on_update(update).await,
+                        Some(Err(e)) => warn!("League client websocket error: {}", e),
+                        None => {
+                            warn!("League client websocket closed, falling back to polling");
+
+                            lcu_events = None;
+                        }
+                    },
+                    _ = self.hotkey_pressed.notified() => self.refresh_champ_select().await,
+                    _ = self.builds_loaded.notified() => self.refresh_champ_select().await,
+                }
+            }
+        }
+    }
+
+    async fn on_update(&mut self, update: LcuUpdate) {
+        match update {
+            LcuUpdate::GameFlowSession(game_flow_session) => {
+                self.on_game_flow_session(game_flow_session).await
+            }
+            LcuUpdate::ChampSelectSession(champ_select_session) => {
+                self.on_champ_select_session(&champ_select_session).await
+            }
+            LcuUpdate::ChampSelectEnded => debug!("Champ select session ended"),
+        }
+    }
+
+    // Polling picks up hotkeys and loaded builds on its own, this is only needed for
+    // websocket updates
+    async fn refresh_champ_select(&mut self) {
+        if self.session.state() != SessionState::ChampSelect {
+            return;
+        }
+
+        match self.lcu_driver.get_champ_select_session().await {
+            Ok(champ_select_session) => self.on_champ_select_session(&champ_select_session).await,
+            Err(e) => warn!("Failed to get champ select session: {}", e),
+        }
+    }
+
+    // Fallback for when the websocket isn't available
+    async fn poll(&mut self) -> Result<()> {
+        let game_flow_session = self
+            .lcu_driver
+            .get_gameflow_session()
+            .await
+            .inspect_err(|e| warn!("Failed to get gameflow session: {}", e))?;
+
+        self.on_game_flow_session(game_flow_session).await;
+
+        if self.session.state() == SessionState::ChampSelect {
+            match self.lcu_driver.get_champ_select_session().await {
+                Ok(champ_select_session) => {
+                    self.on_champ_select_session(&champ_select_session).await
+                }
+                Err(e) => warn!("Failed to get champ select session: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_game_flow_session(&mut self, game_flow_session: GameFlowSession) {
+        let transition = self.session.advance(&game_flow_session.phase);
+
+        // Stored first so the hooks can see the session that caused the transition
+        self.game_flow_session = Some(game_flow_session);
+
+        if let Some(transition) = transition {
+            self.on_transition(transition).await;
+        }
+    }
+
+    async fn on_transition(&mut self, transition: Transition) {
+        if transition.is_expected() {
+            debug!("Session: {} -> {}", transition.from, transition.to);
+        } else {
+            warn!(
+                "Unexpected session change: {} -> {}",
+                transition.from, transition.to
+            );
+        }
+
+        self.on_exit(transition.from).await;
+        self.on_enter(transition.to).await;
+    }
+
+    async fn on_enter(&mut self, state: SessionState) {
+        match state {
+            SessionState::Idle => {
+                self.game_flow_session = None;
+                self.previous_selection = None;
+                self.applied_spells = None;
+            }
+            SessionState::ChampSelect => {
+                // Use the detected position again for every new champ select
+                {
+                    let mut selection = self.hotkeys.write().await;
+                    selection.overridden = false;
+                    selection.rune_page = 0;
+                    selection.active = true;
+                }
+
+                self.previous_selection = None;
+                self.applied_spells = None;
+
+                self.champ_select_count += 1;
+                self.champ_select_span =
+                    info_span!("champ_select", session = self.champ_select_count);
+            }
+            SessionState::InGame => info!("Waiting for game to end..."),
+            SessionState::Reconnect => info!("Waiting to reconnect to the game..."),
+            SessionState::Lobby | SessionState::Loading | SessionState::EndOfGame => (),
+        }
+    }
+
+    async fn on_exit(&mut self, state: SessionState) {
+        if state == SessionState::ChampSelect {
+            self.hotkeys.write().await.active = false;
+            self.champ_select_span = Span::none();
+        }
+    }
+
+    async fn on_champ_select_session(&mut self, champ_select_session: &ChampSelectSession) {
+        if self.session.state() != SessionState::ChampSelect {
+            return;
+        }
+
+        let span = self.champ_select_span.clone();
+
+        if let Err(e) = self
+            .load_champion_runes_and_summoners(champ_select_session)
+            .instrument(span.clone())
+            .await
+        {
+            error!(parent: &span, "{}", e);
+        }
+    }
+
+    /// Remembers where the player put Flash if they moved it after we set the spells.
+    async fn remember_flash_slot(&mut self, champion_id: isize, spells: (isize, isize)) {
+        let Some(applied) = self.applied_spells else {
+            return;
+        };
+
+        if spells == applied.spells {
+            self.applied_spells = Some(AppliedSpells {
+                spells,
+                confirmed: true,
+            });
+
+            return;
+        }
+
+        if !applied.confirmed {
+            debug!("Ignoring summoner spells from before ours were set");
+
+            return;
+        }
+
+        // Only react to each change once
+        self.applied_spells = Some(AppliedSpells {
+            spells,
+            confirmed: true,
+        });
+
+        let Some(slot) = FlashSlot::find(spells.0, spells.1) else {
+            return;
+        };
+
+        if FlashSlot::find(applied.spells.0, applied.spells.1) == Some(slot) {
+            return;
+        }
+
+        let remembered = self
+            .flash_slots
+            .remember(self.my_summoner.summoner_id, champion_id, slot);
+
+        if remembered {
+            info!("Remembering Flash on {}", slot);
+
+            if let Err(e) = self.flash_slots.save(self.config).await {
+                warn!("Failed to save the remembered Flash slots: {}", e);
+            }
+        }
+    }
+
+    async fn load_champion_runes_and_summoners(
+        &mut self,
+        champ_select_session: &ChampSelectSession,
+    ) -> Result<()> {
+        let my_player_selection = champ_select_session
+            .my_team
+            .iter()
+            .find(|p| p.summoner_id == self.my_summoner.summoner_id)
+            .context("Couldn't find current player selection")?;
+
+        /* Must pick a champion first */
+        if my_player_selection.champion_id == 0 {
+            return Ok(());
+        }
+
+        self.remember_flash_slot(
+            my_player_selection.champion_id,
+            (my_player_selection.spell1_id, my_player_selection.spell2_id),
+        )
+        .await;
+
+        // Borrowed after remembering the Flash slot, which needs `self` mutably
+        let game_flow_session = self
+            .game_flow_session
+            .as_ref()
+            .context("Champ select started before the gameflow session was known")?;
+
+        let queue_id = game_flow_session.game_data.queue.id;
+        let supported_queue = Queue::from_queue_id(queue_id);
+        let queue = supported_queue.unwrap_or(Queue::RankedSolo);
+
+        let (position, rune_page_index) = {
+            let mut selection = self.hotkeys.write().await;
+
+            if !selection.overridden {
+                selection.position =
+                    Position::from_assigned_position(&my_player_selection.assigned_position)
+                        .or_else(|| {
+                            self.ugg_build_data
+                                .most_played_position(my_player_selection.champion_id, queue)
+                        })
+                        .unwrap_or(self.config.default_position);
+            }
+
+            (selection.position, selection.rune_page)
+        };
+
+        let opponent_id = self.find_lane_opponent(champ_select_session, queue, position);
+
+        // Fetched in the background, the general build is used until it is available
+        let matchup_build = match opponent_id {
+            Some(opponent_id) => {
+                self.matchups
+                    .get(
+                        my_player_selection.champion_id,
+                        opponent_id,
+                        queue,
+                        position,
+                    )
+                    .await
+            }
+            None => None,
+        };
+
+        let selection = AppliedSelection {
+            champion_id: my_player_selection.champion_id,
+            position,
+            rune_page: rune_page_index,
+            opponent_id,
+            has_matchup_build: matchup_build.is_some(),
+        };
+
+        /* Don't set the same page twice */
+        if self.previous_selection == Some(selection) {
+            return Ok(());
+        }
+
+        if supported_queue.is_none() {
+            warn!(
+                "Unsupported queue: {}, falling back to {} builds",
+                queue_id, queue
+            );
+        }
+
+        info!("Loading {} runes for {}", queue, position);
+
+        let resolved_build = self
+            .ugg_build_data
+            .resolve_build(my_player_selection.champion_id, queue, &position)
+            .context("Couldn't find a build for this champion")?;
+
+        if resolved_build.is_fallback() {
+            warn!(
+                "No {} {} build found, using {} {} ({})",
+                self.localization.champion_name(resolved_build.champion),
+                position,
+                resolved_build.build_data.position,
+                resolved_build.queue,
+                resolved_build.fallback
+            );
+        }
+
+        let skill_order = &resolved_build.build_data.skill_order;
+
+        info!("Skill order: {}", skill_order.max_order_text());
+        info!("Skill path: {}", skill_order.path_text());
+
+        if let Some(matchup_build) = &matchup_build {
+            let opponent_name = self
+                .ugg_build_data
+                .find_champion(matchup_build.opponent_key)
+                .map_or("unknown champion", |c| self.localization.champion_name(c));
+
+            info!("Using matchup build against {}", opponent_name);
+        }
+
+        // The matchup page comes first so it is used until the hotkey cycles away from it
+        let rune_pages = matchup_build
+            .iter()
+            .map(|m| &m.rune_page)
+            .chain(resolved_build.build_data.rune_pages())
+            .collect::<Vec<_>>();
+
+        let rune_page = rune_pages[rune_page_index % rune_pages.len()];
+
+        info!(
+            "Rune page: {} ({:.2}% win rate over {} games)",
+            rune_page.variant,
+            rune_page.stats.win_rate * 100.0,
+            rune_page.stats.games
+        );
+
+        let new_runes_page =
+            resolved_build.perks_page(&self.config.page_prefix, rune_page, &self.localization);
+
+        let ugg_summoner_spells = match &matchup_build {
+            Some(matchup_build) => matchup_build.summoner_spells.clone(),
+            None => resolved_build.summoner_spells().to_owned(),
+        };
+
+        // My selection is our current summoner spells/skins
+        let mut my_selection = MySelection::from(my_player_selection);
+
+        let game_mode = ddragon_game_mode(&game_flow_session.map.game_mode, queue);
+
+        if let Some(summoner_spells) = &self.summoner_spells {
+            // The general build's spells stand in for the matchup's, then the other builds'
+            let alternatives = matchup_build
+                .is_some()
+                .then(|| resolved_build.summoner_spells())
+                .into_iter()
+                .chain(&resolved_build.build_data.alternative_summoner_spells)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let [spell1_id, spell2_id] =
+                summoner_spells.choose(&ugg_summoner_spells, &alternatives, game_mode);
+
+            if spell1_id != Some(ugg_summoner_spells.spell1_id)
+                || spell2_id != Some(ugg_summoner_spells.spell2_id)
+            {
+                info!("Substituted summoner spells not allowed in {}", game_mode);
+            }
+
+            // The client keeps the player's spell in a slot we couldn't fill
+            for (slot, spell_id, ugg_spell_id) in [
+                (1, spell1_id, ugg_summoner_spells.spell1_id),
+                (2, spell2_id, ugg_summoner_spells.spell2_id),
+            ] {
+                if spell_id.is_none() {
+                    warn!(
+                        "No summoner spell allowed in {} to replace {} in slot {}",
+                        game_mode,
+                        summoner_spells
+                            .spell_name(ugg_spell_id)
+                            .map_or_else(|| ugg_spell_id.to_string(), str::to_owned),
+                        slot
+                    );
+                }
+            }
+
+            if let Some(spell1_id) = spell1_id {
+                my_selection.spell1_id = spell1_id;
+            }
+
+            if let Some(spell2_id) = spell2_id {
+                my_selection.spell2_id = spell2_id;
+            }
+        } else {
+            match game_flow_session.map.game_mode.disallowed_summoner_spells() {
+                Some(disallowed_spells) => {
+                    /* If we have an empty list of disallowed_spells spells then we will
+                    not modify anything as we are in an Unknown Gamemode. Otherwise
+                    we can safely modify the spell if isn't in our blacklist. */
+                    if !disallowed_spells.is_empty() {
+                        if !disallowed_spells.contains(&ugg_summoner_spells.spell1_id) {
+                            my_selection.spell1_id = ugg_summoner_spells.spell1_id;
+                        }
+
+                        if !disallowed_spells.contains(&ugg_summoner_spells.spell2_id) {
+                            my_selection.spell2_id = ugg_summoner_spells.spell2_id;
+                        }
+                    }
+                }
+                None => {
+                    //If we have no spells that aren't allowed then set them to the UGG spells
+                    my_selection.spell1_id = ugg_summoner_spells.spell1_id;
+                    my_selection.spell2_id = ugg_summoner_spells.spell2_id;
+                }
+            }
+        }
+
+        let flash_slot = self.flash_slots.preferred(
+            self.config,
+            self.my_summoner.summoner_id,
+            selection.champion_id,
+            Some(resolved_build.champion),
+        );
+
+        (my_selection.spell1_id, my_selection.spell2_id) =
+            flash_slot.arrange(my_selection.spell1_id, my_selection.spell2_id);
+
+        let curr_runes_pages = self
+            .lcu_driver
+            .get_perks_pages()
+            .await?
+            .pages
+            .into_iter()
+            .filter(|p| p.is_deletable)
+            .collect::<Vec<_>>();
+
+        let perks_inventory = self.lcu_driver.get_perks_inventory().await?;
+
+        let plan = RunePagePlan::new(
+            curr_runes_pages,
+            perks_inventory.owned_page_count,
+            self.config,
+        );
+
+        self.save_rune_page(plan, &new_runes_page).await?;
+
+        if self.config.dry_run {
+            info!(
+                "Dry run: would set summoner spells {} and {}",
+                my_selection.spell1_id, my_selection.spell2_id
+            );
+        } else {
+            self.lcu_driver
+                .set_session_my_selection(&my_selection)
+                .await?;
+
+            let spells = (my_selection.spell1_id, my_selection.spell2_id);
+
+            // The client doesn't send an update when the spells didn't change
+            let confirmed = self
+                .applied_spells
+                .is_some_and(|applied| applied.confirmed && applied.spells == spells);
+
+            self.applied_spells = Some(AppliedSpells { spells, confirmed });
+        }
+
+        self.previous_selection = Some(selection);
+
+        Ok(())
+    }
+
+    /// Saves `new_runes_page` in the slot picked by `plan`, or only logs it in a dry run.
+    async fn save_rune_page(&self, plan: RunePagePlan, new_runes_page: &PerksPage) -> Result<()> {
+        if let RunePageSlot::Skip = plan.slot {
+            warn!(
+                "Every rune page slot is taken, set `scratch_page` to a page we may overwrite \
+                 or delete one to use {}",
+                new_runes_page.name
+            );
+        }
+
+        if self.config.dry_run {
+            for change in plan.describe(&new_runes_page.name) {
+                info!("Dry run: would {}", change);
+            }
+
+            return Ok(());
+        }
+
+        for page in plan.stale_pages {
+            debug!("Deleting rune page: {}", page.name);
+
+            self.lcu_driver.delete_perks_page(page.id).await?;
+        }
+
+        match plan.slot {
+            RunePageSlot::Overwrite(page) => {
+                debug!("Overwriting rune page: {}", page.name);
+
+                self.lcu_driver
+                    .update_perks_page(page.id, new_runes_page)
+                    .await?;
+            }
+            RunePageSlot::Create => self.lcu_driver.set_perks_page(new_runes_page).await?,
+            RunePageSlot::DeleteAndCreate(page) => {
+                warn!("Deleting rune page {} to make room", page.name);
+
+                self.lcu_driver.delete_perks_page(page.id).await?;
+                self.lcu_driver.set_perks_page(new_runes_page).await?;
+            }
+            RunePageSlot::Skip => (),
+        }
+
+        Ok(())
+    }
+
+    // Enemy positions are hidden in ranked, so fall back to their champion's most played role
+    fn find_lane_opponent(
+        &self,
+        champ_select_session: &ChampSelectSession,
+        queue: Queue,
+        position: Position,
+    ) -> Option<isize> {
+        if !queue.has_lanes() {
+            return None;
+        }
+
+        let opponents = champ_select_session
+            .their_team
+            .iter()
+            .filter(|p| p.champion_id != 0);
+
+        opponents
+            .clone()
+            .find(|p| Position::from_assigned_position(&p.assigned_position) == Some(position))
+            .or_else(|| {
+                opponents.clone().find(|p| {
+                    self.ugg_build_data
+                        .most_played_position(p.champion_id, queue)
+                        == Some(position)
+                })
+            })
+            .map(|p| p.champion_id)
+    }
+}
+
+// The DDragon name of the game mode, which summoner spells list the modes they're allowed
+// in by. Modes lcu_driver doesn't know are taken from the queue instead.
+fn ddragon_game_mode(game_mode: &GameMode, queue: Queue) -> &'static str {
+    match game_mode {
+        GameMode::Classic => "CLASSIC",
+        GameMode::Aram => "ARAM",
+        GameMode::Urf => "URF",
+        _ => queue.game_mode(),
+    }
+}
@@ -0,0 +1,138 @@
+use app_error::{AppErrorExt, Result};
+use league_helper::config::Config;
+use league_helper::models::ddragon_updater::DDragonUpdater;
+use league_helper::models::localization::Localization;
+use league_helper::models::ugg::build_data::BuildData;
+use league_helper::models::ugg::build_stats::BuildStats;
+use league_helper::models::ugg::item_set::ItemSetStats;
+use league_helper::models::ugg::position::Position;
+use league_helper::models::ugg::queue::Queue;
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+
+use crate::commands::load_localization;
+
+pub async fn run(
+    config: &Config,
+    champion: &str,
+    position: Option<Position>,
+    queue: Queue,
+) -> Result<()> {
+    let ddragon = if config.offline {
+        None
+    } else {
+        DDragonUpdater::new(config).await.ok()
+    };
+
+    let ugg_build_data = match &ddragon {
+        Some(ddragon) => match UggBuildData::load_cached(ddragon, config, None).await {
+            Ok(ugg_build_data) => ugg_build_data,
+            Err(_) => UggBuildData::load_offline(config).await?,
+        },
+        None => UggBuildData::load_offline(config).await?,
+    };
+
+    let (champion, queue_builds) = ugg_build_data
+        .find_champion_by_name(champion)
+        .context(format!("Couldn't find a champion named: {}", champion))?;
+
+    // Names are only a nicety so we don't need them offline
+    let localization = load_localization(config, None).await;
+
+    let champion_name = localization.champion_name(champion);
+
+    let builds = queue_builds
+        .get(&queue)
+        .context(format!("No {} builds found for {}", queue, champion_name))?;
+
+    let mut found = false;
+
+    for build_data in builds
+        .iter()
+        .filter(|b| position.is_none_or(|p| b.position == p))
+    {
+        found = true;
+
+        print_build_data(champion_name, queue, build_data, &localization);
+    }
+
+    if !found {
+        println!(
+            "No {} {} build found for {}",
+            queue,
+            position.unwrap_or(Position::Unknown),
+            champion_name
+        );
+    }
+
+    Ok(())
+}
+
+fn print_build_data(
+    champion_name: &str,
+    queue: Queue,
+    build_data: &BuildData,
+    localization: &Localization,
+) {
+    println!("{} {} ({})", champion_name, build_data.position, queue);
+
+    for rune_page in build_data.rune_pages() {
+        let runes = rune_page
+            .runes
+            .iter()
+            .map(|id| {
+                localization
+                    .rune_name(*id)
+                    .unwrap_or_else(|| id.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        println!(
+            "  {} runes: {} [{}]",
+            rune_page.variant,
+            runes,
+            format_stats(&rune_page.stats)
+        );
+    }
+    println!(
+        "  Summoner spells: {}, {} [{}]",
+        build_data.summoner_spells.spell1_id,
+        build_data.summoner_spells.spell2_id,
+        format_stats(&build_data.summoner_spells.stats)
+    );
+    println!("  Skill order: {}", build_data.skill_order);
+
+    for item_set in &build_data.item_sets {
+        match &item_set.stats {
+            ItemSetStats::Set(stats) => {
+                let items = item_set
+                    .items
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                println!("  {}: {} [{}]", item_set.name, items, format_stats(stats));
+            }
+            ItemSetStats::PerItem(stats) => {
+                println!("  {}:", item_set.name);
+
+                for (item, stats) in item_set.items.iter().zip(stats) {
+                    println!("    {} [{}]", item, format_stats(stats));
+                }
+            }
+        }
+    }
+
+    println!();
+}
+
+fn format_stats(stats: &BuildStats) -> String {
+    format!(
+        "{:.2}% win rate ({:.2}% lower bound) over {} games, {:.2}% pick rate",
+        stats.win_rate * 100.0,
+        stats.win_rate_lower_bound * 100.0,
+        stats.games,
+        stats.pick_rate * 100.0
+    )
+}
@@ -0,0 +1,24 @@
+use app_error::{bail, Result};
+use league_helper::config::Config;
+use league_helper::models::ddragon_updater::DDragonUpdater;
+use league_helper::models::ugg::ugg_build_data::UggBuildData;
+use tracing::info;
+
+pub async fn run(config: &Config) -> Result<()> {
+    if config.offline {
+        bail!("Can't update the build data in offline mode");
+    }
+
+    let ddragon = DDragonUpdater::new(config).await?;
+
+    let ugg_build_data = UggBuildData::update(&ddragon, config).await?;
+
+    info!(
+        "Updated build data for {} champions (patch {}, U.GG overview {}).",
+        ugg_build_data.builds.len(),
+        ugg_build_data.patch_version,
+        ugg_build_data.overview_version
+    );
+
+    Ok(())
+}
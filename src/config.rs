@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use app_error::{bail, AppError, AppErrorExt, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::ddragon_champions::Champion;
+use crate::models::flash_slot::FlashSlot;
+use crate::models::localization::is_valid_locale;
+use crate::models::ugg::position::Position;
+use crate::models::ugg::rank_tier::RankTier;
+use crate::models::ugg::region::Region;
+use crate::util::normalize_name;
+
+const APP_DIR_NAME: &str = "league_helper";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+const MIN_POLL_INTERVAL_MS: u64 = 250;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub region: Region,
+    pub rank_tier: RankTier,
+    pub poll_interval_ms: u64,
+    pub page_prefix: String,
+    pub cache_max_age_hours: u64,
+    pub default_position: Position,
+    pub data_dir: PathBuf,
+    pub offline: bool,
+    pub ddragon_versions_ttl_minutes: u64,
+    // Language for champion and rune names, e.g. ko_KR. Detected from the client if unset
+    pub locale: Option<String>,
+    // Summoner spell key Flash goes on. If unset, where it was last moved by hand
+    pub flash_slot: Option<FlashSlot>,
+    // Flash slot for specific champions by name, e.g. "Lee Sin" = "F"
+    pub champion_flash_slots: BTreeMap<String, FlashSlot>,
+    // Name of a rune page we may overwrite when every slot is taken
+    pub scratch_page: Option<String>,
+    // Delete one of the user's own rune pages when no slot is free and there is no scratch page
+    pub delete_user_pages: bool,
+    // Log the rune page and summoner spell changes instead of making them
+    pub dry_run: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            region: Region::default(),
+            rank_tier: RankTier::default(),
+            poll_interval_ms: 2500,
+            page_prefix: "[LH]".to_owned(),
+            cache_max_age_hours: 48,
+            default_position: Position::Jungle,
+            data_dir: dirs::data_local_dir()
+                .map(|d| d.join(APP_DIR_NAME))
+                .unwrap_or_else(|| PathBuf::from(".")),
+            offline: false,
+            ddragon_versions_ttl_minutes: 60,
+            locale: None,
+            flash_slot: None,
+            champion_flash_slots: BTreeMap::new(),
+            scratch_page: None,
+            delete_user_pages: false,
+            dry_run: false,
+        }
+    }
+}
+
+impl Config {
+    /// The config file location in the platform config directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform has no config directory.
+    pub fn default_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir().context("Failed to find the config directory")?;
+
+        Ok(config_dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    /// Loads the config file at `path`, or the defaults if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't valid TOML for this struct.
+    /// The error message names the file and the offending key.
+    pub async fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = tokio::fs::read_to_string(path).await?;
+
+        toml::from_str(&data)
+            .map_err(|e| AppError::new(format!("Invalid config {}: {}", path.display(), e)))
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the config can't be serialized or written to `path`.
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let data = toml::to_string_pretty(&self).map_err(|e| AppError::new(e.to_string()))?;
+
+        tokio::fs::write(path, data).await?;
+
+        Ok(())
+    }
+
+    /// Checks every value is usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first invalid key.
+    pub fn validate(&self) -> Result<()> {
+        if self.poll_interval_ms < MIN_POLL_INTERVAL_MS {
+            bail!(
+                "Invalid config value for `poll_interval_ms`: must be at least {}",
+                MIN_POLL_INTERVAL_MS
+            );
+        }
+
+        if self.page_prefix.trim().is_empty() {
+            bail!("Invalid config value for `page_prefix`: must not be empty");
+        }
+
+        if self.cache_max_age_hours == 0 {
+            bail!("Invalid config value for `cache_max_age_hours`: must be greater than 0");
+        }
+
+        if self.default_position == Position::Unknown {
+            bail!("Invalid config value for `default_position`: must be a valid position");
+        }
+
+        if let Some(locale) = &self.locale {
+            if !is_valid_locale(locale) {
+                bail!("Invalid config value for `locale`: must look like en_US or ko_KR");
+            }
+        }
+
+        if let Some(scratch_page) = &self.scratch_page {
+            if scratch_page.trim().is_empty() {
+                bail!("Invalid config value for `scratch_page`: must not be empty");
+            }
+
+            if scratch_page.starts_with(&self.page_prefix) {
+                bail!("Invalid config value for `scratch_page`: must not start with `page_prefix`");
+            }
+        }
+
+        if self.data_dir.as_os_str().is_empty() {
+            bail!("Invalid config value for `data_dir`: must not be empty");
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `data_dir` can't be created.
+    pub async fn create_data_dir(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.data_dir)
+            .await
+            .context("Invalid config value for `data_dir`: failed to create directory")?;
+
+        Ok(())
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn cache_max_age(&self) -> Duration {
+        Duration::from_secs(self.cache_max_age_hours * 3600)
+    }
+
+    pub fn ddragon_versions_ttl(&self) -> Duration {
+        Duration::from_secs(self.ddragon_versions_ttl_minutes * 60)
+    }
+
+    pub fn champion_flash_slot(&self, champion: &Champion) -> Option<FlashSlot> {
+        let names = [normalize_name(&champion.name), normalize_name(&champion.id)];
+
+        self.champion_flash_slots
+            .iter()
+            .find(|(name, _)| names.contains(&normalize_name(name)))
+            .map(|(_, slot)| *slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_CONFIG: &str = r#"
+region = "EUW"
+rank_tier = "platinum_plus"
+poll_interval_ms = 1000
+page_prefix = "[LH]"
+cache_max_age_hours = 24
+default_position = "Mid"
+data_dir = "data"
+offline = false
+ddragon_versions_ttl_minutes = 60
+locale = "ko_KR"
+flash_slot = "F"
+scratch_page = "Scratch"
+delete_user_pages = false
+dry_run = true
+
+[champion_flash_slots]
+"Lee Sin" = "D"
+"#;
+
+    fn assert_sample(config: &Config) {
+        assert_eq!(config.region, Region::Euw);
+        assert_eq!(config.rank_tier, RankTier::PlatinumPlus);
+        assert_eq!(config.poll_interval_ms, 1000);
+        assert_eq!(config.default_position, Position::Mid);
+        assert_eq!(config.data_dir, PathBuf::from("data"));
+        assert_eq!(config.locale.as_deref(), Some("ko_KR"));
+        assert_eq!(config.flash_slot, Some(FlashSlot::F));
+        assert_eq!(
+            config.champion_flash_slots.get("Lee Sin"),
+            Some(&FlashSlot::D)
+        );
+        assert_eq!(config.scratch_page.as_deref(), Some("Scratch"));
+        assert!(config.dry_run);
+    }
+
+    #[test]
+    fn round_trips_sample_config() {
+        let config: Config = toml::from_str(SAMPLE_CONFIG).unwrap();
+
+        assert_sample(&config);
+        config.validate().unwrap();
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+
+        // Saved with the names the CLI uses, so the file can be edited by hand
+        assert!(saved.contains(r#"region = "EUW""#));
+        assert!(saved.contains(r#"rank_tier = "platinum_plus""#));
+
+        assert_sample(&toml::from_str(&saved).unwrap());
+    }
+
+    #[test]
+    fn accepts_names_saved_by_older_versions() {
+        let config: Config =
+            toml::from_str("region = \"Euw\"\nrank_tier = \"EmeraldPlus\"").unwrap();
+
+        assert_eq!(config.region, Region::Euw);
+        assert_eq!(config.rank_tier, RankTier::EmeraldPlus);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(toml::from_str::<Config>("regoin = \"EUW\"").is_err());
+    }
+}
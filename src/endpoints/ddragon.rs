@@ -3,21 +3,36 @@ const DDRAGON_URL: &str = "https://ddragon.leagueoflegends.com";
 #[allow(unused)]
 pub enum DDragonEndpoint<'a> {
     Version,
-    ChampionData(&'a str),
-    RunesData(&'a str),
+    // Version, locale
+    ChampionData(&'a str, &'a str),
+    RunesData(&'a str, &'a str),
+    ItemData(&'a str, &'a str),
+    SummonerData(&'a str, &'a str),
 }
 
 impl DDragonEndpoint<'_> {
     pub fn url(&self) -> String {
         match self {
             DDragonEndpoint::Version => format!("{}/api/versions.json", DDRAGON_URL),
-            DDragonEndpoint::ChampionData(version) => {
-                format!("{}/cdn/{}/data/en_US/champion.json", DDRAGON_URL, version)
+            DDragonEndpoint::ChampionData(version, locale) => {
+                format!(
+                    "{}/cdn/{}/data/{}/champion.json",
+                    DDRAGON_URL, version, locale
+                )
+            }
+            DDragonEndpoint::RunesData(version, locale) => {
+                format!(
+                    "{}/cdn/{}/data/{}/runesReforged.json",
+                    DDRAGON_URL, version, locale
+                )
+            }
+            DDragonEndpoint::ItemData(version, locale) => {
+                format!("{}/cdn/{}/data/{}/item.json", DDRAGON_URL, version, locale)
             }
-            DDragonEndpoint::RunesData(version) => {
+            DDragonEndpoint::SummonerData(version, locale) => {
                 format!(
-                    "{}/cdn/{}/data/en_US/runesReforged.json",
-                    DDRAGON_URL, version
+                    "{}/cdn/{}/data/{}/summoner.json",
+                    DDRAGON_URL, version, locale
                 )
             }
         }
@@ -1,9 +1,16 @@
+use crate::models::ugg::build_variant::BuildVariant;
+use crate::models::ugg::queue::Queue;
+
 const UGG_URL: &str = "https://u.gg";
 
 pub enum UggEndpoint<'a> {
     HomePage,
     BaseUrl(&'a str),
-    ChampionData(&'a str, isize, &'a str),
+    ChampionData(&'a str, Queue, isize, &'a str),
+    // Patch, queue, champion key, opponent champion key, overview version
+    MatchupData(&'a str, Queue, isize, isize, &'a str),
+    // Patch, build variant, queue, champion key, overview version
+    VariantData(&'a str, BuildVariant, Queue, isize, &'a str),
 }
 
 impl UggEndpoint<'_> {
@@ -13,10 +20,45 @@ impl UggEndpoint<'_> {
             UggEndpoint::BaseUrl(api_version) => {
                 format!("https://stats2.u.gg/lol/{}", api_version)
             }
-            UggEndpoint::ChampionData(patch_version, champion_key, overview_version) => {
+            UggEndpoint::ChampionData(patch_version, queue, champion_key, overview_version) => {
+                format!(
+                    "/overview/{}/{}/{}/{}.json",
+                    patch_version,
+                    queue.ugg_key(),
+                    champion_key,
+                    overview_version
+                )
+            }
+            UggEndpoint::MatchupData(
+                patch_version,
+                queue,
+                champion_key,
+                opponent_key,
+                overview_version,
+            ) => {
+                format!(
+                    "/matchup_overview/{}/{}/{}/{}/{}.json",
+                    patch_version,
+                    queue.ugg_key(),
+                    champion_key,
+                    opponent_key,
+                    overview_version
+                )
+            }
+            UggEndpoint::VariantData(
+                patch_version,
+                variant,
+                queue,
+                champion_key,
+                overview_version,
+            ) => {
                 format!(
-                    "/overview/{}/ranked_solo_5x5/{}/{}.json",
-                    patch_version, champion_key, overview_version
+                    "/{}/{}/{}/{}/{}.json",
+                    variant.ugg_key(),
+                    patch_version,
+                    queue.ugg_key(),
+                    champion_key,
+                    overview_version
                 )
             }
         }
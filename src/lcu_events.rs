@@ -0,0 +1,109 @@
+use app_error::{AppError, Result};
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use lcu_driver::endpoints::champ_select::ChampSelectSession;
+use lcu_driver::endpoints::gameflow::GameFlowSession;
+use lcu_driver::{Initialized, LcuDriver};
+use serde::Deserialize;
+use tokio_tungstenite::tungstenite::Message;
+
+const GAMEFLOW_SESSION_URI: &str = "/lol-gameflow/v1/session";
+const CHAMP_SELECT_SESSION_URI: &str = "/lol-champ-select/v1/session";
+
+// WAMP 1.0 opcodes used by the LCU websocket
+const WAMP_SUBSCRIBE: u8 = 5;
+const WAMP_EVENT: u8 = 8;
+
+#[derive(Debug)]
+pub enum LcuUpdate {
+    GameFlowSession(GameFlowSession),
+    ChampSelectSession(ChampSelectSession),
+    ChampSelectEnded,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonApiEvent {
+    uri: String,
+    event_type: String,
+    data: serde_json::Value,
+}
+
+/// The gameflow and champ select session updates pushed by the League client.
+pub struct LcuEvents {
+    messages: BoxStream<'static, Result<String>>,
+}
+
+impl LcuEvents {
+    pub async fn connect(lcu_driver: &LcuDriver<Initialized>) -> Result<Self> {
+        let websocket = lcu_driver.connect_websocket().await?;
+
+        let (mut sink, stream) = websocket.split();
+
+        for uri in [GAMEFLOW_SESSION_URI, CHAMP_SELECT_SESSION_URI] {
+            let subscribe = serde_json::json!([WAMP_SUBSCRIBE, Self::event_name(uri)]);
+
+            sink.send(Message::text(subscribe.to_string()))
+                .await
+                .map_err(|e| AppError::new(format!("Failed to subscribe to {}: {}", uri, e)))?;
+        }
+
+        let messages = stream
+            .filter_map(|message| async {
+                match message {
+                    Ok(Message::Text(text)) => Some(Ok(text.to_string())),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(AppError::new(e.to_string()))),
+                }
+            })
+            .boxed();
+
+        Ok(Self { messages })
+    }
+
+    // e.g. /lol-gameflow/v1/session -> OnJsonApiEvent_lol-gameflow_v1_session
+    fn event_name(uri: &str) -> String {
+        format!("OnJsonApiEvent{}", uri.replace('/', "_"))
+    }
+
+    /// Waits for the next update we are interested in. Returns `None` once the
+    /// connection has been closed.
+    pub async fn next(&mut self) -> Option<Result<LcuUpdate>> {
+        while let Some(message) = self.messages.next().await {
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if let Some(update) = Self::parse_update(&message) {
+                return Some(update);
+            }
+        }
+
+        None
+    }
+
+    fn parse_update(message: &str) -> Option<Result<LcuUpdate>> {
+        let (opcode, _, event) =
+            serde_json::from_str::<(u8, String, JsonApiEvent)>(message).ok()?;
+
+        if opcode != WAMP_EVENT {
+            return None;
+        }
+
+        let update = match event.uri.as_str() {
+            GAMEFLOW_SESSION_URI => {
+                serde_json::from_value(event.data).map(LcuUpdate::GameFlowSession)
+            }
+            CHAMP_SELECT_SESSION_URI if event.event_type == "Delete" => {
+                Ok(LcuUpdate::ChampSelectEnded)
+            }
+            CHAMP_SELECT_SESSION_URI => {
+                serde_json::from_value(event.data).map(LcuUpdate::ChampSelectSession)
+            }
+            _ => return None,
+        };
+
+        Some(update.map_err(|e| AppError::new(format!("Failed to parse {}: {}", event.uri, e))))
+    }
+}
@@ -0,0 +1,25 @@
+//! Builds, rune pages and summoner spells from U.GG for the League of Legends client.
+//!
+//! [`UggBuildData`] downloads and caches the U.GG statistics for every champion, using
+//! [`DDragonUpdater`] for the champion and rune data. The stored [`BuildData`] can be
+//! exported as a [`LeagueItemSet`] or turned into a rune page with
+//! [`UggBuildData::resolve_build`].
+//!
+//! Every fallible function returns [`Result`], whose [`AppError`] carries a message
+//! describing what failed.
+
+pub use app_error::{AppError, Result};
+
+pub use crate::config::Config;
+pub use crate::models::ddragon_updater::DDragonUpdater;
+pub use crate::models::league_item_set::LeagueItemSet;
+pub use crate::models::ugg::build_data::BuildData;
+pub use crate::models::ugg::ugg_build_data::UggBuildData;
+pub use crate::models::ugg::ugg_client::UggClient;
+pub use crate::models::ugg::ugg_role_data::UggRoleData;
+
+pub mod config;
+mod endpoints;
+pub mod models;
+pub mod session;
+mod util;
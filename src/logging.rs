@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+const LOG_FILE_NAME: &str = "league_helper.log";
+
+/// Logs to the console at the requested level and everything from debug upwards to a
+/// daily log file in `log_dir`. The returned guard must be kept alive to flush the file.
+pub fn init(log_dir: &Path, verbose: bool, quiet: bool) -> WorkerGuard {
+    let console_level = if verbose {
+        LevelFilter::DEBUG
+    } else if quiet {
+        LevelFilter::WARN
+    } else {
+        LevelFilter::INFO
+    };
+
+    let (file_writer, guard) =
+        tracing_appender::non_blocking(tracing_appender::rolling::daily(log_dir, LOG_FILE_NAME));
+
+    // Keep dependencies quiet unless something goes wrong
+    let targets = |level| {
+        Targets::new()
+            .with_target(env!("CARGO_PKG_NAME"), level)
+            .with_default(LevelFilter::WARN)
+    };
+
+    tracing_subscriber::registry()
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_filter(targets(console_level)),
+        )
+        .with(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(file_writer)
+                .with_filter(targets(LevelFilter::DEBUG)),
+        )
+        .init();
+
+    guard
+}
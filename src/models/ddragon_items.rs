@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ugg::queue::Queue;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemsData {
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub version: String,
+    #[serde(deserialize_with = "item_map_deserializer")]
+    #[serde(rename = "data")]
+    pub items: HashMap<isize, ItemData>,
+}
+
+fn item_map_deserializer<'de, D>(deserializer: D) -> Result<HashMap<isize, ItemData>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let data: HashMap<String, ItemData> = HashMap::deserialize(deserializer)?;
+
+    data.into_iter()
+        .map(|(id, item)| {
+            id.parse()
+                .map(|id| (id, item))
+                .map_err(|_| serde::de::Error::custom(format!("Invalid item id: {}", id)))
+        })
+        .collect()
+}
+
+impl ItemsData {
+    /// Whether `item_id` can be bought on the map `queue` is played on. Items removed
+    /// from the game, hidden from the shop or only given by an ally (e.g. Ornn upgrades)
+    /// are not.
+    pub fn is_purchasable(&self, item_id: isize, queue: Queue) -> bool {
+        self.items.get(&item_id).is_some_and(|item| {
+            item.gold.purchasable
+                && item.in_store.unwrap_or(true)
+                && item.required_ally.is_none()
+                && item
+                    .maps
+                    .get(queue.ddragon_map_id())
+                    .copied()
+                    .unwrap_or(false)
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemData {
+    pub name: String,
+    #[serde(default)]
+    pub into: Vec<String>,
+    #[serde(default)]
+    pub from: Vec<String>,
+    pub gold: Gold,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Map id -> whether the item is available there
+    pub maps: HashMap<String, bool>,
+    pub in_store: Option<bool>,
+    pub required_ally: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Gold {
+    pub base: isize,
+    pub purchasable: bool,
+    pub total: isize,
+    pub sell: isize,
+}
@@ -5,6 +5,17 @@ pub struct RunesData {
     pub runes_data: Vec<RuneData>,
 }
 
+impl RunesData {
+    pub fn rune_name(&self, rune_id: isize) -> Option<String> {
+        self.runes_data
+            .iter()
+            .flat_map(|r| &r.slots)
+            .flat_map(|s| &s.runes)
+            .find(|r| r.id == rune_id)
+            .map(|r| r.name.clone())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RuneData {
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ugg::summoner_spells::SummonerSpells;
+
+/// Every summoner spell from DDragon's `summoner.json`, keyed by spell id.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummonerSpellCatalogue {
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub version: String,
+    #[serde(deserialize_with = "spell_map_deserializer")]
+    #[serde(rename = "data")]
+    pub spells: HashMap<isize, SummonerSpellData>,
+}
+
+fn spell_map_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<isize, SummonerSpellData>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let data: HashMap<String, SummonerSpellData> = HashMap::deserialize(deserializer)?;
+
+    data.into_values()
+        .map(|spell| {
+            spell
+                .key
+                .parse()
+                .map(|key| (key, spell))
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+impl SummonerSpellCatalogue {
+    /// Whether `spell_id` can be taken in `game_mode`, e.g. CLASSIC or ARAM.
+    pub fn is_allowed(&self, spell_id: isize, game_mode: &str) -> bool {
+        self.spells
+            .get(&spell_id)
+            .is_some_and(|spell| spell.modes.iter().any(|m| m == game_mode))
+    }
+
+    /// The name of `spell_id`, e.g. Flash.
+    pub fn spell_name(&self, spell_id: isize) -> Option<&str> {
+        self.spells.get(&spell_id).map(|spell| spell.name.as_str())
+    }
+
+    /// The spells to take in `game_mode`: each of `preferred` that is allowed, otherwise
+    /// the first allowed spell from `alternatives` that isn't already taken. A slot is
+    /// `None` if nothing suitable is allowed.
+    pub fn choose(
+        &self,
+        preferred: &SummonerSpells,
+        alternatives: &[SummonerSpells],
+        game_mode: &str,
+    ) -> [Option<isize>; 2] {
+        let mut chosen = [preferred.spell1_id, preferred.spell2_id]
+            .map(|spell_id| self.is_allowed(spell_id, game_mode).then_some(spell_id));
+
+        let candidates = alternatives
+            .iter()
+            .flat_map(|s| [s.spell1_id, s.spell2_id])
+            .collect::<Vec<_>>();
+
+        for slot in 0..chosen.len() {
+            if chosen[slot].is_none() {
+                chosen[slot] = candidates.iter().copied().find(|&spell_id| {
+                    self.is_allowed(spell_id, game_mode) && !chosen.contains(&Some(spell_id))
+                });
+            }
+        }
+
+        chosen
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpellData {
+    pub id: String,
+    pub name: String,
+    pub key: String,
+    // Game modes the spell can be taken in, e.g. CLASSIC, ARAM, URF
+    pub modes: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ugg::build_stats::BuildStats;
+
+    // Flash, Ignite and Smite are allowed in Summoner's Rift, only Flash and Ignite in ARAM
+    fn catalogue() -> SummonerSpellCatalogue {
+        let spell = |key: isize, name: &str, modes: &[&str]| {
+            (
+                key,
+                SummonerSpellData {
+                    id: format!("Summoner{}", name),
+                    name: name.to_owned(),
+                    key: key.to_string(),
+                    modes: modes.iter().map(|&m| m.to_owned()).collect(),
+                },
+            )
+        };
+
+        SummonerSpellCatalogue {
+            data_type: "summoner".to_owned(),
+            version: "14.1.1".to_owned(),
+            spells: HashMap::from([
+                spell(4, "Flash", &["CLASSIC", "ARAM"]),
+                spell(14, "Ignite", &["CLASSIC", "ARAM"]),
+                spell(11, "Smite", &["CLASSIC"]),
+            ]),
+        }
+    }
+
+    fn spells(spell1_id: isize, spell2_id: isize) -> SummonerSpells {
+        SummonerSpells::new(spell1_id, spell2_id, BuildStats::new(100, 50, 100))
+    }
+
+    #[test]
+    fn keeps_allowed_spells() {
+        let chosen = catalogue().choose(&spells(4, 11), &[spells(4, 14)], "CLASSIC");
+
+        assert_eq!(chosen, [Some(4), Some(11)]);
+    }
+
+    #[test]
+    fn substitutes_disallowed_spell() {
+        let chosen = catalogue().choose(&spells(4, 11), &[spells(4, 14)], "ARAM");
+
+        assert_eq!(chosen, [Some(4), Some(14)]);
+    }
+
+    #[test]
+    fn no_legal_substitute() {
+        let chosen = catalogue().choose(&spells(4, 11), &[spells(11, 4)], "ARAM");
+
+        assert_eq!(chosen, [Some(4), None]);
+    }
+}
@@ -1,39 +1,178 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use app_error::AppErrorExt;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
+use tracing::warn;
 
+use crate::config::Config;
 use crate::endpoints::ddragon::DDragonEndpoint;
 use crate::models::ddragon_champions::ChampionData;
+use crate::models::ddragon_items::ItemsData;
 use crate::models::ddragon_runes_reforged::{RuneData, RunesData};
+use crate::models::ddragon_summoner_spells::SummonerSpellCatalogue;
+use crate::models::file_info::FileInfo;
+use crate::models::localization::DEFAULT_LOCALE;
 use crate::Result;
 
+const DDRAGON_CACHE_DIR: &str = "ddragon";
+const VERSIONS_FILE_NAME: &str = "versions.json";
+const CHAMPIONS_FILE_NAME: &str = "champion.json";
+const RUNES_FILE_NAME: &str = "runesReforged.json";
+const ITEMS_FILE_NAME: &str = "item.json";
+const SUMMONER_SPELLS_FILE_NAME: &str = "summoner.json";
+
 #[derive(Debug)]
 pub struct DDragonUpdater {
     client: Client,
+    cache_dir: PathBuf,
     pub version: String,
 }
 
 impl DDragonUpdater {
-    pub async fn new() -> Result<Self> {
+    /// Finds the latest DDragon version, using the cached version list while it is
+    /// within the configured TTL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version list can't be downloaded and no cached list exists.
+    pub async fn new(config: &Config) -> Result<Self> {
         let client = Client::new();
 
-        let res = client
-            .get(DDragonEndpoint::Version.url())
-            .send()
-            .await?
-            .text()
-            .await?;
+        let cache_dir = config.data_dir.join(DDRAGON_CACHE_DIR);
+
+        tokio::fs::create_dir_all(&cache_dir).await?;
+
+        let versions =
+            Self::load_versions(&client, &cache_dir, config.ddragon_versions_ttl()).await?;
 
-        let version = serde_json::from_str::<Vec<String>>(&res)?
+        let version = versions
             .first()
-            .expect("Missing version data from DDragon.")
+            .context("Missing version data from DDragon.")?
             .to_string();
 
-        Ok(DDragonUpdater { client, version })
+        let updater = DDragonUpdater {
+            client,
+            cache_dir,
+            version,
+        };
+
+        if let Err(e) = updater.remove_old_versions().await {
+            warn!("Failed to remove old DDragon data: {}", e);
+        }
+
+        Ok(updater)
     }
 
+    async fn load_versions(
+        client: &Client,
+        cache_dir: &Path,
+        ttl: Duration,
+    ) -> Result<Vec<String>> {
+        let versions_path = cache_dir.join(VERSIONS_FILE_NAME);
+
+        let cached_versions = match FileInfo::read(&versions_path).await {
+            Ok(file_info) => {
+                let is_fresh = file_info
+                    .metadata
+                    .modified()
+                    .ok()
+                    .and_then(|m| m.elapsed().ok())
+                    .is_some_and(|age| age < ttl);
+
+                serde_json::from_slice::<Vec<String>>(&file_info.data)
+                    .ok()
+                    .map(|versions| (versions, is_fresh))
+            }
+            Err(_) => None,
+        };
+
+        if let Some((versions, true)) = cached_versions {
+            return Ok(versions);
+        }
+
+        let downloaded = async {
+            let res = client
+                .get(DDragonEndpoint::Version.url())
+                .send()
+                .await?
+                .text()
+                .await?;
+
+            let versions = serde_json::from_str::<Vec<String>>(&res)?;
+
+            tokio::fs::write(&versions_path, res).await?;
+
+            Result::Ok(versions)
+        }
+        .await;
+
+        match (downloaded, cached_versions) {
+            (Ok(versions), _) => Ok(versions),
+            // Still usable when DDragon can't be reached
+            (Err(e), Some((versions, _))) => {
+                warn!(
+                    "Failed to download DDragon versions, using cached list: {}",
+                    e
+                );
+
+                Ok(versions)
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    // Cached data is stored per version so anything from an older patch can go
+    async fn remove_old_versions(&self) -> Result<()> {
+        let mut entries = tokio::fs::read_dir(&self.cache_dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() && entry.file_name() != self.version.as_str() {
+                tokio::fs::remove_dir_all(entry.path()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The champions in [`DEFAULT_LOCALE`], which everything we store is keyed by.
     pub async fn download_latest_champions(&self) -> Result<ChampionData> {
+        self.download_champions(DEFAULT_LOCALE).await
+    }
+
+    /// The runes in [`DEFAULT_LOCALE`], which everything we store is keyed by.
+    pub async fn download_latest_runes(&self) -> Result<RunesData> {
+        self.download_runes(DEFAULT_LOCALE).await
+    }
+
+    /// The item catalogue in [`DEFAULT_LOCALE`], used to check U.GG's item ids.
+    pub async fn download_latest_items(&self) -> Result<ItemsData> {
+        self.call_cached_endpoint(
+            &DDragonEndpoint::ItemData(&self.version, DEFAULT_LOCALE),
+            DEFAULT_LOCALE,
+            ITEMS_FILE_NAME,
+        )
+        .await
+    }
+
+    /// The summoner spells and the game modes they are allowed in.
+    pub async fn download_latest_summoner_spells(&self) -> Result<SummonerSpellCatalogue> {
+        self.call_cached_endpoint(
+            &DDragonEndpoint::SummonerData(&self.version, DEFAULT_LOCALE),
+            DEFAULT_LOCALE,
+            SUMMONER_SPELLS_FILE_NAME,
+        )
+        .await
+    }
+
+    pub async fn download_champions(&self, locale: &str) -> Result<ChampionData> {
         let mut data: ChampionData = self
-            .call_endpoint(&DDragonEndpoint::ChampionData(&self.version))
+            .call_cached_endpoint(
+                &DDragonEndpoint::ChampionData(&self.version, locale),
+                locale,
+                CHAMPIONS_FILE_NAME,
+            )
             .await?;
 
         data.champion_list.sort_by(|a, b| a.id.cmp(&b.id));
@@ -41,9 +180,13 @@ impl DDragonUpdater {
         Ok(data)
     }
 
-    pub async fn download_latest_runes(&self) -> Result<RunesData> {
+    pub async fn download_runes(&self, locale: &str) -> Result<RunesData> {
         let data: Vec<RuneData> = self
-            .call_endpoint(&DDragonEndpoint::RunesData(&self.version))
+            .call_cached_endpoint(
+                &DDragonEndpoint::RunesData(&self.version, locale),
+                locale,
+                RUNES_FILE_NAME,
+            )
             .await?;
 
         let data = RunesData { runes_data: data };
@@ -51,12 +194,29 @@ impl DDragonUpdater {
         Ok(data)
     }
 
-    async fn call_endpoint<T: DeserializeOwned>(
+    async fn call_cached_endpoint<T: DeserializeOwned>(
         &self,
         endpoint: &DDragonEndpoint<'_>,
+        locale: &str,
+        file_name: &str,
     ) -> Result<T> {
+        let version_dir = self.cache_dir.join(&self.version).join(locale);
+        let cache_path = version_dir.join(file_name);
+
+        if let Ok(data) = tokio::fs::read(&cache_path).await {
+            match serde_json::from_slice::<T>(&data) {
+                Ok(data) => return Ok(data),
+                Err(e) => warn!("Ignoring invalid cached {}: {}", file_name, e),
+            }
+        }
+
         let res = self.client.get(endpoint.url()).send().await?.text().await?;
 
-        Ok(serde_json::from_str::<T>(&res)?)
+        let data = serde_json::from_str::<T>(&res)?;
+
+        tokio::fs::create_dir_all(&version_dir).await?;
+        tokio::fs::write(&cache_path, res).await?;
+
+        Ok(data)
     }
 }
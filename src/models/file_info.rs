@@ -1,5 +1,10 @@
 use std::fs::Metadata;
 use std::path::Path;
+use std::time::Duration;
+
+use app_error::{bail, AppErrorExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tokio::io::AsyncReadExt;
 
 use crate::Result;
@@ -21,4 +26,34 @@ impl FileInfo {
 
         Ok(Self { metadata, data })
     }
+
+    /// Reads a JSON cache file, unless it was written more than `max_age` ago.
+    pub async fn read_json<T: DeserializeOwned>(
+        path: impl AsRef<Path>,
+        max_age: Duration,
+    ) -> Result<T> {
+        let file = Self::read(path).await?;
+
+        if file.metadata.modified()?.elapsed()? >= max_age {
+            bail!("Cached file is outdated.");
+        }
+
+        let value =
+            serde_json::from_slice(&file.data).context("Cached file is in an outdated format")?;
+
+        Ok(value)
+    }
+
+    /// Writes a JSON cache file, creating its directory if needed.
+    pub async fn write_json<T: Serialize>(path: impl AsRef<Path>, value: &T) -> Result<()> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(path, serde_json::to_vec(value)?).await?;
+
+        Ok(())
+    }
 }
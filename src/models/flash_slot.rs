@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use app_error::{AppErrorExt, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::models::ddragon_champions::Champion;
+
+pub const FLASH_SPELL_ID: isize = 4;
+
+const FLASH_SLOTS_FILE_NAME: &str = "flash_slots.json";
+
+/// The summoner spell key Flash goes on.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    strum::Display,
+    strum::EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum FlashSlot {
+    #[default]
+    D,
+    F,
+}
+
+impl FlashSlot {
+    /// The slot Flash is in, if it is one of the spells.
+    pub fn find(spell1_id: isize, spell2_id: isize) -> Option<Self> {
+        if spell1_id == FLASH_SPELL_ID {
+            Some(FlashSlot::D)
+        } else if spell2_id == FLASH_SPELL_ID {
+            Some(FlashSlot::F)
+        } else {
+            None
+        }
+    }
+
+    /// The spells swapped if needed to put Flash in this slot.
+    pub fn arrange(self, spell1_id: isize, spell2_id: isize) -> (isize, isize) {
+        match Self::find(spell1_id, spell2_id) {
+            Some(slot) if slot != self => (spell2_id, spell1_id),
+            _ => (spell1_id, spell2_id),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SummonerFlashSlots {
+    last: Option<FlashSlot>,
+    champions: HashMap<isize, FlashSlot>,
+}
+
+/// Where each summoner last put Flash themselves in champ select, overall and for
+/// each champion. Stored in the data directory so it carries over between runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FlashSlotMemory {
+    summoners: HashMap<isize, SummonerFlashSlots>,
+}
+
+impl FlashSlotMemory {
+    /// Loads the remembered slots, or nothing if none have been saved yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be read or parsed.
+    pub async fn load(config: &Config) -> Result<Self> {
+        let path = Self::file_path(config);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = tokio::fs::read(&path).await?;
+
+        let memory = serde_json::from_slice(&data).context("Invalid remembered flash slots")?;
+
+        Ok(memory)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub async fn save(&self, config: &Config) -> Result<()> {
+        tokio::fs::write(Self::file_path(config), serde_json::to_vec(self)?).await?;
+
+        Ok(())
+    }
+
+    /// The slot to put Flash in: where it was last put on this champion, otherwise the
+    /// configured slot for the champion, the configured slot for every champion, where it
+    /// was last put on any champion and finally D.
+    pub fn preferred(
+        &self,
+        config: &Config,
+        summoner_id: isize,
+        champion_key: isize,
+        champion: Option<&Champion>,
+    ) -> FlashSlot {
+        let remembered = self.summoners.get(&summoner_id);
+
+        remembered
+            .and_then(|s| s.champions.get(&champion_key).copied())
+            .or_else(|| champion.and_then(|c| config.champion_flash_slot(c)))
+            .or(config.flash_slot)
+            .or_else(|| remembered.and_then(|s| s.last))
+            .unwrap_or_default()
+    }
+
+    /// Records that the summoner moved Flash to `slot`, returning whether that changed
+    /// anything.
+    pub fn remember(&mut self, summoner_id: isize, champion_key: isize, slot: FlashSlot) -> bool {
+        let summoner = self.summoners.entry(summoner_id).or_default();
+
+        let changed =
+            summoner.last != Some(slot) || summoner.champions.get(&champion_key) != Some(&slot);
+
+        summoner.last = Some(slot);
+        summoner.champions.insert(champion_key, slot);
+
+        changed
+    }
+
+    fn file_path(config: &Config) -> PathBuf {
+        config.data_dir.join(FLASH_SLOTS_FILE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMONER_ID: isize = 1;
+    const LEE_SIN: isize = 64;
+    const AHRI: isize = 103;
+
+    const HEAL: isize = 7;
+
+    #[test]
+    fn find() {
+        assert_eq!(FlashSlot::find(FLASH_SPELL_ID, HEAL), Some(FlashSlot::D));
+        assert_eq!(FlashSlot::find(HEAL, FLASH_SPELL_ID), Some(FlashSlot::F));
+        assert_eq!(FlashSlot::find(HEAL, 11), None);
+    }
+
+    #[test]
+    fn arrange() {
+        assert_eq!(
+            FlashSlot::F.arrange(FLASH_SPELL_ID, HEAL),
+            (HEAL, FLASH_SPELL_ID)
+        );
+        assert_eq!(
+            FlashSlot::F.arrange(HEAL, FLASH_SPELL_ID),
+            (HEAL, FLASH_SPELL_ID)
+        );
+        assert_eq!(
+            FlashSlot::D.arrange(HEAL, FLASH_SPELL_ID),
+            (FLASH_SPELL_ID, HEAL)
+        );
+        // Nothing to move without Flash
+        assert_eq!(FlashSlot::D.arrange(HEAL, 11), (HEAL, 11));
+    }
+
+    #[test]
+    fn remember() {
+        let mut memory = FlashSlotMemory::default();
+
+        assert!(memory.remember(SUMMONER_ID, LEE_SIN, FlashSlot::F));
+        assert!(!memory.remember(SUMMONER_ID, LEE_SIN, FlashSlot::F));
+        // Same last slot, but new for this champion
+        assert!(memory.remember(SUMMONER_ID, AHRI, FlashSlot::F));
+        assert!(memory.remember(SUMMONER_ID, AHRI, FlashSlot::D));
+    }
+
+    #[test]
+    fn preferred_defaults_to_d() {
+        let memory = FlashSlotMemory::default();
+
+        assert_eq!(
+            memory.preferred(&Config::default(), SUMMONER_ID, LEE_SIN, None),
+            FlashSlot::D
+        );
+    }
+
+    #[test]
+    fn preferred_uses_last_moved_slot_when_not_configured() {
+        let mut memory = FlashSlotMemory::default();
+        memory.remember(SUMMONER_ID, LEE_SIN, FlashSlot::F);
+
+        assert_eq!(
+            memory.preferred(&Config::default(), SUMMONER_ID, AHRI, None),
+            FlashSlot::F
+        );
+        // Other summoners on the same computer keep their own slots
+        assert_eq!(
+            memory.preferred(&Config::default(), 2, AHRI, None),
+            FlashSlot::D
+        );
+    }
+
+    #[test]
+    fn configured_slot_beats_last_moved_slot() {
+        let mut memory = FlashSlotMemory::default();
+        memory.remember(SUMMONER_ID, LEE_SIN, FlashSlot::F);
+
+        let config = Config {
+            flash_slot: Some(FlashSlot::D),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            memory.preferred(&config, SUMMONER_ID, AHRI, None),
+            FlashSlot::D
+        );
+        // Moving it on a champion still sticks for that champion
+        assert_eq!(
+            memory.preferred(&config, SUMMONER_ID, LEE_SIN, None),
+            FlashSlot::F
+        );
+    }
+
+    #[tokio::test]
+    async fn save_and_load() {
+        let data_dir =
+            std::env::temp_dir().join(format!("league_helper-flash-slots-{}", std::process::id()));
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let config = Config {
+            data_dir: data_dir.clone(),
+            ..Config::default()
+        };
+
+        assert!(FlashSlotMemory::load(&config)
+            .await
+            .unwrap()
+            .summoners
+            .is_empty());
+
+        let mut memory = FlashSlotMemory::default();
+        memory.remember(SUMMONER_ID, LEE_SIN, FlashSlot::F);
+        memory.save(&config).await.unwrap();
+
+        let loaded = FlashSlotMemory::load(&config).await.unwrap();
+
+        std::fs::remove_dir_all(&data_dir).unwrap();
+
+        assert_eq!(
+            loaded.preferred(&config, SUMMONER_ID, LEE_SIN, None),
+            FlashSlot::F
+        );
+        assert_eq!(
+            loaded.preferred(&config, SUMMONER_ID, AHRI, None),
+            FlashSlot::F
+        );
+    }
+}
@@ -1,8 +1,18 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
 use crate::models::ddragon_champions::Champion;
+use crate::models::localization::Localization;
 use crate::models::ugg::build_data::BuildData;
 use crate::models::ugg::item_set::ItemSet;
+use crate::models::ugg::queue::Queue;
+use crate::models::ugg::skill_order::{Skill, SkillOrder};
+
+// Item sets can only hold items, so each skill gets a block with one Poro-Snax per level
+// it is taken at, numbered with that level. It can't be bought, so it never looks like
+// a real purchase suggestion
+const SKILL_PLACEHOLDER_ITEM_ID: &str = "2052";
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,10 +44,26 @@ impl<'a> Default for LeagueItemSet<'a> {
 }
 
 impl<'a> LeagueItemSet<'a> {
-    pub fn from_build_data(build_data: &'a mut BuildData, champion: &'a Champion) -> Self {
+    pub fn from_build_data(
+        build_data: &'a BuildData,
+        champion: &'a Champion,
+        queue: Queue,
+        page_prefix: &str,
+        localization: &Localization,
+    ) -> Self {
         Self {
-            title: format!("[LH] - {} {}", champion.name, build_data.position),
-            blocks: build_data.item_sets.iter().map(Block::from).collect(),
+            title: format!(
+                "{} - {} {} ({})",
+                page_prefix,
+                localization.champion_name(champion),
+                build_data.position,
+                queue
+            ),
+            map: queue.item_set_map(),
+            blocks: Block::from_skill_order(&build_data.skill_order)
+                .into_iter()
+                .chain(build_data.item_sets.iter().map(Block::from))
+                .collect(),
             champion_key: &champion.id,
             ..Self::default()
         }
@@ -53,7 +79,7 @@ pub struct Block<'a> {
     pub show_if_summoner_spell: &'a str,
     pub hide_if_summoner_spell: &'a str,
     #[serde(rename = "type")]
-    pub type_field: &'a str,
+    pub type_field: Cow<'a, str>,
     pub items: Vec<Item>,
 }
 
@@ -65,7 +91,7 @@ impl<'a> Default for Block<'a> {
             max_summoner_level: -1,
             show_if_summoner_spell: "",
             hide_if_summoner_spell: "",
-            type_field: "",
+            type_field: Cow::Borrowed(""),
             items: vec![],
         }
     }
@@ -74,13 +100,39 @@ impl<'a> Default for Block<'a> {
 impl<'a> From<&'a ItemSet> for Block<'a> {
     fn from(item_set: &'a ItemSet) -> Self {
         Self {
-            type_field: &item_set.name,
+            type_field: Cow::Borrowed(&item_set.name),
             items: item_set.items.iter().map(Item::from).collect(),
             ..Self::default()
         }
     }
 }
 
+impl Block<'_> {
+    /// One block per skill, e.g. "Q - Q>E>W", with an entry for every level it is
+    /// taken at.
+    pub fn from_skill_order(skill_order: &SkillOrder) -> Vec<Self> {
+        [Skill::Q, Skill::W, Skill::E, Skill::R]
+            .into_iter()
+            .filter_map(|skill| {
+                let items = (1..)
+                    .zip(&skill_order.path)
+                    .filter(|(_, s)| **s == skill)
+                    .map(|(level, _)| Item {
+                        id: SKILL_PLACEHOLDER_ITEM_ID.to_owned(),
+                        count: level,
+                    })
+                    .collect::<Vec<_>>();
+
+                (!items.is_empty()).then(|| Self {
+                    type_field: Cow::Owned(format!("{} - {}", skill, skill_order.max_order_text())),
+                    items,
+                    ..Self::default()
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Item {
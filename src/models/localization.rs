@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::models::ddragon_champions::Champion;
+use crate::models::ddragon_runes_reforged::RunesData;
+use crate::models::ddragon_updater::DDragonUpdater;
+use crate::Result;
+
+/// The locale builds are downloaded and stored in, so ids and cache files don't change
+/// with the client language.
+pub const DEFAULT_LOCALE: &str = "en_US";
+
+/// Champion and rune names in the client's language, for anything the user sees.
+#[derive(Debug)]
+pub struct Localization {
+    pub locale: String,
+    champion_names: HashMap<isize, String>,
+    runes_data: Option<RunesData>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            locale: DEFAULT_LOCALE.to_owned(),
+            champion_names: HashMap::new(),
+            runes_data: None,
+        }
+    }
+}
+
+impl Localization {
+    /// # Errors
+    ///
+    /// Returns an error if the champion or rune data for `locale` can't be downloaded.
+    pub async fn load(ddragon: &DDragonUpdater, locale: &str) -> Result<Self> {
+        let champion_names = ddragon
+            .download_champions(locale)
+            .await?
+            .champion_list
+            .into_iter()
+            .map(|c| (c.key, c.name))
+            .collect();
+
+        let runes_data = ddragon.download_runes(locale).await?;
+
+        Ok(Self {
+            locale: locale.to_owned(),
+            champion_names,
+            runes_data: Some(runes_data),
+        })
+    }
+
+    /// The localized name, or the stored English name if we don't have one.
+    pub fn champion_name<'a>(&'a self, champion: &'a Champion) -> &'a str {
+        self.champion_names
+            .get(&champion.key)
+            .unwrap_or(&champion.name)
+    }
+
+    pub fn rune_name(&self, rune_id: isize) -> Option<String> {
+        self.runes_data.as_ref()?.rune_name(rune_id)
+    }
+}
+
+/// Whether `locale` looks like a DDragon locale, e.g. en_US or ko_KR.
+pub fn is_valid_locale(locale: &str) -> bool {
+    matches!(
+        locale.split_once('_'),
+        Some((language, country))
+            if language.len() == 2
+                && country.len() == 2
+                && language.chars().all(|c| c.is_ascii_lowercase())
+                && country.chars().all(|c| c.is_ascii_uppercase())
+    )
+}
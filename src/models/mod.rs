@@ -1,6 +1,11 @@
 pub mod ddragon_champions;
+pub mod ddragon_items;
 pub mod ddragon_runes_reforged;
+pub mod ddragon_summoner_spells;
 pub mod ddragon_updater;
 pub mod file_info;
+pub mod flash_slot;
 pub mod league_item_set;
+pub mod localization;
+pub mod rune_page_plan;
 pub mod ugg;
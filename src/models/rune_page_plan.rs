@@ -0,0 +1,228 @@
+use lcu_driver::endpoints::perks::PerksPage;
+
+use crate::config::Config;
+
+/// Where a new rune page goes, given the pages the client already has.
+#[derive(Debug)]
+pub enum RunePageSlot {
+    /// Replace a page in place, either one of ours or the configured scratch page.
+    Overwrite(PerksPage),
+    /// There is a free slot.
+    Create,
+    /// Delete a page the user made to free a slot, only when `delete_user_pages` is set.
+    DeleteAndCreate(PerksPage),
+    /// There is no page we are allowed to replace.
+    Skip,
+}
+
+/// The changes needed to save a rune page without touching pages the user cares about.
+#[derive(Debug)]
+pub struct RunePagePlan {
+    pub slot: RunePageSlot,
+    /// Our other pages, left over from earlier runs.
+    pub stale_pages: Vec<PerksPage>,
+}
+
+impl RunePagePlan {
+    /// Picks a slot among `pages`, the user's editable rune pages. One of our own pages
+    /// is reused first, then a free slot, then the scratch page. A user page is only
+    /// deleted when the config allows it.
+    pub fn new(pages: Vec<PerksPage>, owned_page_count: isize, config: &Config) -> Self {
+        let page_count = pages.len() as isize;
+
+        let (mut own_pages, mut user_pages): (Vec<_>, Vec<_>) = pages
+            .into_iter()
+            .partition(|p| p.name.starts_with(&config.page_prefix));
+
+        if !own_pages.is_empty() {
+            let page = own_pages.remove(0);
+
+            return Self {
+                slot: RunePageSlot::Overwrite(page),
+                stale_pages: own_pages,
+            };
+        }
+
+        let slot = if page_count < owned_page_count {
+            RunePageSlot::Create
+        } else if let Some(index) = user_pages
+            .iter()
+            .position(|p| Some(&p.name) == config.scratch_page.as_ref())
+        {
+            RunePageSlot::Overwrite(user_pages.remove(index))
+        } else if config.delete_user_pages && !user_pages.is_empty() {
+            RunePageSlot::DeleteAndCreate(user_pages.remove(0))
+        } else {
+            RunePageSlot::Skip
+        };
+
+        Self {
+            slot,
+            stale_pages: Vec::new(),
+        }
+    }
+    /// The changes the plan makes to save `new_page_name`, as logged in a dry run.
+    pub fn describe(&self, new_page_name: &str) -> Vec<String> {
+        let mut changes = self
+            .stale_pages
+            .iter()
+            .map(|page| format!("delete rune page {}", page.name))
+            .collect::<Vec<_>>();
+
+        match &self.slot {
+            RunePageSlot::Overwrite(page) => changes.push(format!(
+                "overwrite rune page {} with {}",
+                page.name, new_page_name
+            )),
+            RunePageSlot::Create => changes.push(format!("create rune page {}", new_page_name)),
+            RunePageSlot::DeleteAndCreate(page) => changes.push(format!(
+                "delete rune page {} to create {}",
+                page.name, new_page_name
+            )),
+            RunePageSlot::Skip => (),
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNED_PAGE_COUNT: isize = 3;
+
+    fn page(id: isize, name: &str) -> PerksPage {
+        PerksPage {
+            id,
+            name: name.to_owned(),
+            ..PerksPage::default()
+        }
+    }
+
+    fn names(pages: &[PerksPage]) -> Vec<&str> {
+        pages.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    fn full_pages() -> Vec<PerksPage> {
+        vec![page(1, "Aatrox"), page(2, "Scratch"), page(3, "Zed")]
+    }
+
+    #[test]
+    fn reuses_own_page_and_deletes_the_others() {
+        let pages = vec![
+            page(1, "Aatrox"),
+            page(2, "[LH] Lee Sin Jungle"),
+            page(3, "[LH] Ahri Mid"),
+        ];
+
+        let plan = RunePagePlan::new(pages, OWNED_PAGE_COUNT, &Config::default());
+
+        assert!(matches!(plan.slot, RunePageSlot::Overwrite(p) if p.id == 2));
+        assert_eq!(names(&plan.stale_pages), vec!["[LH] Ahri Mid"]);
+    }
+
+    #[test]
+    fn creates_page_when_a_slot_is_free() {
+        let pages = vec![page(1, "Aatrox"), page(2, "Scratch")];
+
+        let config = Config {
+            scratch_page: Some("Scratch".to_owned()),
+            delete_user_pages: true,
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(pages, OWNED_PAGE_COUNT, &config);
+
+        assert!(matches!(plan.slot, RunePageSlot::Create));
+        assert!(plan.stale_pages.is_empty());
+    }
+
+    #[test]
+    fn overwrites_scratch_page_when_full() {
+        let config = Config {
+            scratch_page: Some("Scratch".to_owned()),
+            delete_user_pages: true,
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &config);
+
+        assert!(matches!(plan.slot, RunePageSlot::Overwrite(p) if p.id == 2));
+        assert!(plan.stale_pages.is_empty());
+    }
+
+    #[test]
+    fn skips_when_full_by_default() {
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &Config::default());
+
+        assert!(matches!(plan.slot, RunePageSlot::Skip));
+        assert!(plan.stale_pages.is_empty());
+    }
+
+    #[test]
+    fn skips_when_scratch_page_is_missing() {
+        let config = Config {
+            scratch_page: Some("Missing".to_owned()),
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &config);
+
+        assert!(matches!(plan.slot, RunePageSlot::Skip));
+    }
+
+    #[test]
+    fn deletes_user_page_only_when_allowed() {
+        let config = Config {
+            delete_user_pages: true,
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &config);
+
+        assert!(matches!(plan.slot, RunePageSlot::DeleteAndCreate(p) if p.id == 1));
+        assert!(plan.stale_pages.is_empty());
+    }
+
+    #[test]
+    fn describes_changes_for_dry_run() {
+        let pages = vec![
+            page(1, "[LH] Lee Sin Jungle"),
+            page(2, "[LH] Ahri Mid"),
+            page(3, "Zed"),
+        ];
+
+        let config = Config {
+            dry_run: true,
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(pages, OWNED_PAGE_COUNT, &config);
+
+        assert_eq!(
+            plan.describe("[LH] Ahri Mid"),
+            vec![
+                "delete rune page [LH] Ahri Mid",
+                "overwrite rune page [LH] Lee Sin Jungle with [LH] Ahri Mid",
+            ]
+        );
+
+        let config = Config {
+            dry_run: true,
+            delete_user_pages: true,
+            ..Config::default()
+        };
+
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &config);
+
+        assert_eq!(
+            plan.describe("[LH] Ahri Mid"),
+            vec!["delete rune page Aatrox to create [LH] Ahri Mid"]
+        );
+
+        let plan = RunePagePlan::new(full_pages(), OWNED_PAGE_COUNT, &Config::default());
+
+        assert!(plan.describe("[LH] Ahri Mid").is_empty());
+    }
+}
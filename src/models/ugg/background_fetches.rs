@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, Notify};
+use tracing::Instrument;
+
+enum Fetch<V> {
+    Loading,
+    Done(Option<V>),
+}
+
+/// Results of fetches that run in the background, so champ select never waits on the
+/// network. Each key is fetched once, and `loaded` is notified whenever a fetch finds
+/// something so it can be applied straight away.
+pub struct BackgroundFetches<K, V> {
+    results: Arc<Mutex<HashMap<K, Fetch<V>>>>,
+    loaded: Arc<Notify>,
+}
+
+impl<K, V> BackgroundFetches<K, V>
+where
+    K: Clone + Eq + Hash + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    pub fn new(loaded: Arc<Notify>) -> Self {
+        Self {
+            results: Arc::new(Mutex::new(HashMap::new())),
+            loaded,
+        }
+    }
+
+    /// The result for `key` if it has been fetched. Otherwise starts `fetch` in the
+    /// background, unless it is already running, and returns `None`.
+    pub async fn get_or_fetch<F, Fut>(&self, key: K, fetch: F) -> Option<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<V>> + Send + 'static,
+    {
+        {
+            let mut results = self.results.lock().await;
+
+            match results.get(&key) {
+                Some(Fetch::Done(value)) => return value.clone(),
+                Some(Fetch::Loading) => return None,
+                None => results.insert(key.clone(), Fetch::Loading),
+            };
+        }
+
+        let results = self.results.clone();
+        let loaded = self.loaded.clone();
+        let fetch = fetch();
+
+        tokio::spawn(
+            async move {
+                let value = fetch.await;
+                let found = value.is_some();
+
+                results.lock().await.insert(key, Fetch::Done(value));
+
+                if found {
+                    loaded.notify_one();
+                }
+            }
+            .in_current_span(),
+        );
+
+        None
+    }
+}
@@ -3,18 +3,48 @@ use std::cmp::Ordering;
 use float_ord::FloatOrd;
 use serde::{Deserialize, Serialize};
 
+use crate::models::ddragon_items::ItemsData;
 use crate::models::ugg::item_set::ItemSet;
 use crate::models::ugg::position::Position;
+use crate::models::ugg::queue::Queue;
 use crate::models::ugg::rune_page::RunePage;
+use crate::models::ugg::skill_order::SkillOrder;
 use crate::models::ugg::summoner_spells::SummonerSpells;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BuildData {
     pub position: Position,
     pub rune_page: RunePage,
+    /// Other rune pages for the same role, i.e. the highest win rate one
+    #[serde(default)]
+    pub alternative_rune_pages: Vec<RunePage>,
     pub item_sets: Vec<ItemSet>,
-    pub skill_order: String,
+    pub skill_order: SkillOrder,
     pub summoner_spells: SummonerSpells,
+    /// Spells from other builds for the same role, most played first, to use when one of
+    /// the recommended spells isn't allowed
+    #[serde(default)]
+    pub alternative_summoner_spells: Vec<SummonerSpells>,
+}
+
+impl BuildData {
+    /// Drops items that can't be bought in `queue`, and any item set left empty.
+    pub fn retain_purchasable_items(&mut self, items_data: &ItemsData, queue: Queue) -> Vec<isize> {
+        let mut removed = Vec::new();
+
+        for item_set in &mut self.item_sets {
+            removed.extend(item_set.retain_items(|item| items_data.is_purchasable(item, queue)));
+        }
+
+        self.item_sets.retain(|item_set| !item_set.items.is_empty());
+
+        removed
+    }
+
+    /// The recommended rune page followed by the alternatives.
+    pub fn rune_pages(&self) -> impl Iterator<Item = &RunePage> {
+        std::iter::once(&self.rune_page).chain(&self.alternative_rune_pages)
+    }
 }
 
 impl std::cmp::Eq for BuildData {}
@@ -27,7 +57,8 @@ impl std::cmp::PartialEq for BuildData {
 
 impl std::cmp::Ord for BuildData {
     fn cmp(&self, other: &Self) -> Ordering {
-        FloatOrd(other.rune_page.win_rate).cmp(&FloatOrd(self.rune_page.win_rate))
+        FloatOrd(other.rune_page.stats.win_rate_lower_bound)
+            .cmp(&FloatOrd(self.rune_page.stats.win_rate_lower_bound))
     }
 }
 
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::calc_win_rate;
+
+/// How often part of a build was played and won in the selected region/rank tier.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct BuildStats {
+    pub games: isize,
+    pub wins: isize,
+    /// `wins / games`
+    pub win_rate: f32,
+    /// The Wilson score lower bound of the win rate, so small samples rank lower
+    pub win_rate_lower_bound: f32,
+    /// Share of the champion's games in this role that used this part of the build
+    pub pick_rate: f32,
+}
+
+impl BuildStats {
+    pub fn new(games: isize, wins: isize, role_games: isize) -> Self {
+        let ratio = |n: isize, d: isize| if d > 0 { n as f32 / d as f32 } else { 0.0 };
+
+        Self {
+            games,
+            wins,
+            win_rate: ratio(wins, games),
+            win_rate_lower_bound: calc_win_rate(wins as f32, games as f32),
+            pick_rate: ratio(games, role_games),
+        }
+    }
+}
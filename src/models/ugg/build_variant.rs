@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// The builds U.GG has besides the recommended one, each in its own overview file with
+/// the same layout. Only used to find alternatives to the recommended build.
+#[derive(
+    Clone, Copy, Debug, strum::Display, strum::EnumIter, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum BuildVariant {
+    #[strum(to_string = "AP")]
+    Ap,
+    #[strum(to_string = "AD")]
+    Ad,
+    #[strum(to_string = "Tank")]
+    Tank,
+    #[strum(to_string = "Crit")]
+    Crit,
+    #[strum(to_string = "Lethality")]
+    Lethality,
+    #[strum(to_string = "On-Hit")]
+    OnHit,
+}
+
+impl BuildVariant {
+    // The data set U.GG uses in the variant's overview url
+    pub fn ugg_key(&self) -> &'static str {
+        match self {
+            BuildVariant::Ap => "ap-overview",
+            BuildVariant::Ad => "ad-overview",
+            BuildVariant::Tank => "tank-overview",
+            BuildVariant::Crit => "crit-overview",
+            BuildVariant::Lethality => "lethality-overview",
+            BuildVariant::OnHit => "onhit-overview",
+        }
+    }
+}
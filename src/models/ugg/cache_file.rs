@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use app_error::Result;
+use strum::IntoEnumIterator;
+
+use crate::models::ugg::rank_tier::RankTier;
+use crate::models::ugg::region::Region;
+
+const CACHE_FILE_PREFIX: &str = "ugg-builds-";
+const CACHE_FILE_SUFFIX: &str = ".json.sz";
+
+#[derive(Debug)]
+pub struct CacheFile {
+    pub path: PathBuf,
+    pub patch_version: String,
+    pub region: Region,
+    pub rank_tier: RankTier,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl CacheFile {
+    pub fn file_name(patch_version: &str, region: Region, rank_tier: RankTier) -> String {
+        format!(
+            "{}{}-{}-{}{}",
+            CACHE_FILE_PREFIX,
+            patch_version,
+            region.ugg_key(),
+            rank_tier.ugg_key(),
+            CACHE_FILE_SUFFIX
+        )
+    }
+
+    /// Lists every build data cache file in `data_dir`, newest first.
+    pub fn list(data_dir: &Path) -> Result<Vec<Self>> {
+        let mut cache_files = Vec::new();
+
+        if !data_dir.exists() {
+            return Ok(cache_files);
+        }
+
+        for entry in std::fs::read_dir(data_dir)? {
+            let entry = entry?;
+
+            let Some(file_name) = entry.file_name().to_str().map(|f| f.to_owned()) else {
+                continue;
+            };
+
+            let Some((patch_version, region, rank_tier)) = Self::parse_file_name(&file_name) else {
+                continue;
+            };
+
+            let metadata = entry.metadata()?;
+
+            cache_files.push(CacheFile {
+                path: entry.path(),
+                patch_version,
+                region,
+                rank_tier,
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+
+        cache_files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+
+        Ok(cache_files)
+    }
+
+    fn parse_file_name(file_name: &str) -> Option<(String, Region, RankTier)> {
+        let name = file_name
+            .strip_prefix(CACHE_FILE_PREFIX)?
+            .strip_suffix(CACHE_FILE_SUFFIX)?;
+
+        let mut parts = name.rsplitn(3, '-');
+
+        let rank_tier_key = parts.next()?;
+        let region_key = parts.next()?;
+        let patch_version = parts.next()?;
+
+        let region = Region::iter().find(|r| r.ugg_key() == region_key)?;
+        let rank_tier = RankTier::iter().find(|r| r.ugg_key() == rank_tier_key)?;
+
+        Some((patch_version.to_owned(), region, rank_tier))
+    }
+}
@@ -1,7 +1,47 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::ugg::build_stats::BuildStats;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ItemSetStats {
+    /// Stats for buying the whole set, e.g. the starting items
+    Set(BuildStats),
+    /// Stats for each item, in the same order as `ItemSet::items`
+    PerItem(Vec<BuildStats>),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemSet {
     pub name: String,
     pub items: Vec<isize>,
+    pub stats: ItemSetStats,
+}
+
+impl ItemSet {
+    /// Removes the items `keep` rejects along with their stats, returning the removed ids.
+    pub fn retain_items(&mut self, keep: impl Fn(isize) -> bool) -> Vec<isize> {
+        let mut removed = Vec::new();
+
+        if let ItemSetStats::PerItem(stats) = &mut self.stats {
+            let mut i = 0;
+
+            stats.retain(|_| {
+                let retain = keep(self.items[i]);
+                i += 1;
+                retain
+            });
+        }
+
+        self.items.retain(|&item| {
+            let retain = keep(item);
+
+            if !retain {
+                removed.push(item);
+            }
+
+            retain
+        });
+
+        removed
+    }
 }
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use app_error::{AppError, Result};
+use tokio::sync::Mutex;
+
+use crate::config::Config;
+use crate::models::ddragon_runes_reforged::RunesData;
+use crate::models::ddragon_updater::DDragonUpdater;
+use crate::models::ugg::ugg_client::UggClient;
+
+// How long a failure to reach U.GG is remembered before trying again
+const RETRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+enum ClientState {
+    Uninitialized,
+    Ready(Arc<(UggClient, RunesData)>),
+    Failed { error: String, at: Instant },
+}
+
+/// A U.GG client and the rune data to check its pages against, created the first time
+/// a build is fetched on demand. Failures are remembered for a while so each request
+/// doesn't repeat the DDragon and U.GG home page calls.
+pub struct LazyUggClient {
+    config: Config,
+    state: Mutex<ClientState>,
+}
+
+impl LazyUggClient {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: config.clone(),
+            state: Mutex::new(ClientState::Uninitialized),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the client can't be created, or couldn't be recently.
+    pub async fn get(&self) -> Result<Arc<(UggClient, RunesData)>> {
+        // Held while connecting so concurrent fetches share one attempt
+        let mut state = self.state.lock().await;
+
+        match &*state {
+            ClientState::Ready(client) => return Ok(client.clone()),
+            ClientState::Failed { error, at } if at.elapsed() < RETRY_INTERVAL => {
+                return Err(AppError::new(format!("U.GG is unavailable: {}", error)));
+            }
+            _ => (),
+        }
+
+        match self.connect().await {
+            Ok(client) => {
+                let client = Arc::new(client);
+
+                *state = ClientState::Ready(client.clone());
+
+                Ok(client)
+            }
+            Err(e) => {
+                *state = ClientState::Failed {
+                    error: e.to_string(),
+                    at: Instant::now(),
+                };
+
+                Err(e)
+            }
+        }
+    }
+
+    async fn connect(&self) -> Result<(UggClient, RunesData)> {
+        let ddragon = DDragonUpdater::new(&self.config).await?;
+        let runes_data = ddragon.download_latest_runes().await?;
+        let ugg_client = UggClient::new(&self.config).await?;
+
+        Ok((ugg_client, runes_data))
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ugg::position::Position;
+use crate::models::ugg::queue::Queue;
+use crate::models::ugg::rune_page::RunePage;
+use crate::models::ugg::summoner_spells::SummonerSpells;
+
+/// The runes and summoner spells for one champion against a specific lane opponent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatchupBuild {
+    pub champion_key: isize,
+    pub opponent_key: isize,
+    pub queue: Queue,
+    pub position: Position,
+    pub rune_page: RunePage,
+    pub summoner_spells: SummonerSpells,
+}
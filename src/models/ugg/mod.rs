@@ -1,8 +1,24 @@
+pub mod background_fetches;
 pub mod build_data;
+pub mod build_stats;
+pub mod build_variant;
+pub mod cache_file;
 pub mod item_set;
+pub mod lazy_ugg_client;
+pub mod matchup_build;
 pub mod position;
+pub mod queue;
+pub mod rank_tier;
+pub mod region;
+pub mod resolved_build;
 pub mod rune_page;
+pub mod skill_order;
 pub mod summoner_spells;
+#[cfg(test)]
+mod test_fixtures;
 pub mod ugg_build_data;
 pub mod ugg_client;
+pub mod ugg_matchup_data;
+pub mod ugg_matchups;
+pub mod ugg_overview;
 pub mod ugg_role_data;
@@ -1,8 +1,18 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Clone, Copy, Debug, strum::Display, strum::EnumString, Eq, PartialEq, Serialize, Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    strum::Display,
+    strum::EnumString,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
+#[strum(ascii_case_insensitive)]
 pub enum Position {
     Unknown = 0,
     Jungle = 1,
@@ -26,6 +36,18 @@ impl From<isize> for Position {
 }
 
 impl Position {
+    // Maps the `assignedPosition` from the champ select session
+    pub fn from_assigned_position(assigned_position: &str) -> Option<Self> {
+        match assigned_position.to_lowercase().as_str() {
+            "top" => Some(Position::Top),
+            "jungle" => Some(Position::Jungle),
+            "middle" | "mid" => Some(Position::Mid),
+            "bottom" | "bot" => Some(Position::Bottom),
+            "utility" | "support" => Some(Position::Support),
+            _ => None,
+        }
+    }
+
     pub fn next(&mut self) -> bool {
         let curr = *self as isize;
 
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Queue {
+    RankedSolo,
+    RankedFlex,
+    NormalDraft,
+    NormalBlind,
+    Aram,
+    Arena,
+}
+
+impl Queue {
+    // The queue name U.GG uses in its overview urls
+    pub fn ugg_key(&self) -> &'static str {
+        match self {
+            Queue::RankedSolo => "ranked_solo_5x5",
+            Queue::RankedFlex => "ranked_flex_sr",
+            Queue::NormalDraft => "normal_draft_5x5",
+            Queue::NormalBlind => "normal_blind_5x5",
+            Queue::Aram => "normal_aram",
+            Queue::Arena => "arena",
+        }
+    }
+
+    // https://static.developer.riotgames.com/docs/lol/queues.json
+    pub fn from_queue_id(queue_id: isize) -> Option<Self> {
+        match queue_id {
+            420 => Some(Queue::RankedSolo),
+            440 => Some(Queue::RankedFlex),
+            400 | 490 => Some(Queue::NormalDraft),
+            430 => Some(Queue::NormalBlind),
+            450 => Some(Queue::Aram),
+            1700 | 1710 => Some(Queue::Arena),
+            _ => None,
+        }
+    }
+
+    // Whether players have a lane opponent to look up matchups against
+    pub fn has_lanes(&self) -> bool {
+        !matches!(self, Queue::Aram | Queue::Arena)
+    }
+
+    // The map id DDragon uses for item availability
+    pub fn ddragon_map_id(&self) -> &'static str {
+        match self {
+            Queue::Aram => "12",
+            Queue::Arena => "30",
+            _ => "11",
+        }
+    }
+
+    // The game mode the client reports for this queue, as used in DDragon's spell modes
+    pub fn game_mode(&self) -> &'static str {
+        match self {
+            Queue::Aram => "ARAM",
+            Queue::Arena => "CHERRY",
+            _ => "CLASSIC",
+        }
+    }
+
+    // Whether a build from `other` suits this queue, i.e. same map and lanes
+    pub fn is_compatible_with(&self, other: Queue) -> bool {
+        self.ddragon_map_id() == other.ddragon_map_id() && self.has_lanes() == other.has_lanes()
+    }
+
+    // The map an item set for this queue should be shown on
+    pub fn item_set_map(&self) -> &'static str {
+        match self {
+            Queue::Aram => "HA",
+            Queue::Arena => "any",
+            _ => "SR",
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+// Serde uses the same names as the CLI, accepting the variant names older versions saved
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum RankTier {
+    #[default]
+    #[strum(to_string = "Platinum+", serialize = "platinum_plus")]
+    #[serde(rename = "platinum_plus", alias = "PlatinumPlus")]
+    PlatinumPlus,
+    #[strum(to_string = "Emerald+", serialize = "emerald_plus")]
+    #[serde(rename = "emerald_plus", alias = "EmeraldPlus")]
+    EmeraldPlus,
+    #[strum(to_string = "Diamond+", serialize = "diamond_plus")]
+    #[serde(rename = "diamond_plus", alias = "DiamondPlus")]
+    DiamondPlus,
+    #[strum(to_string = "Master+", serialize = "master_plus")]
+    #[serde(rename = "master_plus", alias = "MasterPlus")]
+    MasterPlus,
+    #[strum(to_string = "Challenger", serialize = "challenger")]
+    #[serde(rename = "challenger", alias = "Challenger")]
+    Challenger,
+    #[strum(to_string = "All", serialize = "all")]
+    #[serde(rename = "all", alias = "All")]
+    All,
+}
+
+impl RankTier {
+    // The rank key U.GG uses in its overview data
+    pub fn ugg_key(&self) -> &'static str {
+        match self {
+            RankTier::Challenger => "1",
+            RankTier::All => "8",
+            RankTier::PlatinumPlus => "10",
+            RankTier::DiamondPlus => "11",
+            RankTier::MasterPlus => "14",
+            RankTier::EmeraldPlus => "17",
+        }
+    }
+}
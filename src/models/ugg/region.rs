@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+// Serde uses the same names as the CLI, accepting the variant names older versions saved
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Region {
+    #[strum(serialize = "NA")]
+    #[serde(rename = "NA", alias = "Na")]
+    Na,
+    #[strum(serialize = "EUW")]
+    #[serde(rename = "EUW", alias = "Euw")]
+    Euw,
+    #[strum(serialize = "KR")]
+    #[serde(rename = "KR", alias = "Kr")]
+    Kr,
+    #[strum(serialize = "EUNE")]
+    #[serde(rename = "EUNE", alias = "Eune")]
+    Eune,
+    #[strum(serialize = "BR")]
+    #[serde(rename = "BR", alias = "Br")]
+    Br,
+    #[strum(serialize = "LAN")]
+    #[serde(rename = "LAN", alias = "Lan")]
+    Lan,
+    #[strum(serialize = "LAS")]
+    #[serde(rename = "LAS", alias = "Las")]
+    Las,
+    #[strum(serialize = "OCE")]
+    #[serde(rename = "OCE", alias = "Oce")]
+    Oce,
+    #[strum(serialize = "RU")]
+    #[serde(rename = "RU", alias = "Ru")]
+    Ru,
+    #[strum(serialize = "TR")]
+    #[serde(rename = "TR", alias = "Tr")]
+    Tr,
+    #[strum(serialize = "JP")]
+    #[serde(rename = "JP", alias = "Jp")]
+    Jp,
+    #[default]
+    #[strum(serialize = "World")]
+    World,
+}
+
+impl Region {
+    // The region key U.GG uses in its overview data
+    pub fn ugg_key(&self) -> &'static str {
+        match self {
+            Region::Na => "1",
+            Region::Euw => "2",
+            Region::Kr => "3",
+            Region::Eune => "4",
+            Region::Br => "5",
+            Region::Lan => "6",
+            Region::Las => "7",
+            Region::Oce => "8",
+            Region::Ru => "9",
+            Region::Tr => "10",
+            Region::Jp => "11",
+            Region::World => "12",
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use lcu_driver::endpoints::perks::PerksPage;
+
+use crate::models::ddragon_champions::Champion;
+use crate::models::localization::Localization;
+use crate::models::ugg::build_data::BuildData;
+use crate::models::ugg::position::Position;
+use crate::models::ugg::queue::Queue;
+use crate::models::ugg::rune_page::{RunePage, RunePageVariant};
+use crate::models::ugg::summoner_spells::SummonerSpells;
+
+#[derive(Clone, Copy, Debug, strum::Display, Eq, PartialEq)]
+pub enum BuildFallback {
+    #[strum(to_string = "exact")]
+    Exact,
+    #[strum(to_string = "most popular role")]
+    MostPopularRole,
+    #[strum(to_string = "same role in another queue")]
+    OtherQueue,
+    #[strum(to_string = "any available role")]
+    AnyAvailable,
+}
+
+#[derive(Debug)]
+pub struct ResolvedBuild<'a> {
+    pub champion: &'a Champion,
+    pub queue: Queue,
+    pub requested_position: Position,
+    pub build_data: &'a BuildData,
+    pub fallback: BuildFallback,
+}
+
+impl ResolvedBuild<'_> {
+    pub fn is_fallback(&self) -> bool {
+        self.fallback != BuildFallback::Exact
+    }
+
+    /// Whether the build comes from a different queue than the one being played.
+    pub fn is_other_queue(&self) -> bool {
+        matches!(
+            self.fallback,
+            BuildFallback::OtherQueue | BuildFallback::AnyAvailable
+        )
+    }
+
+    /// The perks page for `rune_page`, one of this build's rune pages or a matchup page.
+    pub fn perks_page(
+        &self,
+        page_prefix: &str,
+        rune_page: &RunePage,
+        localization: &Localization,
+    ) -> PerksPage {
+        let mut name = format!(
+            "{} {} {}",
+            page_prefix,
+            localization.champion_name(self.champion),
+            self.build_data.position
+        );
+
+        if self.build_data.position != self.requested_position {
+            name.push_str(&format!(" (no {})", self.requested_position));
+        }
+
+        if self.is_other_queue() {
+            name.push_str(&format!(" ({})", self.queue));
+        }
+
+        if rune_page.variant != RunePageVariant::Recommended {
+            name.push_str(&format!(" - {}", rune_page.variant));
+        }
+
+        PerksPage {
+            name,
+            primary_style_id: rune_page.primary_tree,
+            selected_perk_ids: rune_page.runes.clone(),
+            sub_style_id: rune_page.secondary_tree,
+            ..PerksPage::default()
+        }
+    }
+
+    pub fn summoner_spells(&self) -> &SummonerSpells {
+        &self.build_data.summoner_spells
+    }
+}
@@ -1,9 +1,22 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::ugg::build_stats::BuildStats;
+
+#[derive(Clone, Copy, Debug, strum::Display, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RunePageVariant {
+    #[strum(to_string = "Recommended")]
+    Recommended,
+    #[strum(to_string = "Highest Win Rate")]
+    HighestWinRate,
+    #[strum(to_string = "Matchup")]
+    Matchup,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RunePage {
     pub runes: Vec<isize>,
     pub primary_tree: isize,
     pub secondary_tree: isize,
-    pub win_rate: f32,
+    pub variant: RunePageVariant,
+    pub stats: BuildStats,
 }
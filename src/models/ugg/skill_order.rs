@@ -0,0 +1,51 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone, Copy, Debug, strum::Display, strum::EnumString, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub enum Skill {
+    Q,
+    W,
+    E,
+    R,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SkillOrder {
+    /// The skill to level up at each champion level, starting at level 1.
+    pub path: Vec<Skill>,
+    /// The order the basic skills are maxed in, e.g. Q, E, W.
+    pub max_order: Vec<Skill>,
+}
+
+impl SkillOrder {
+    pub fn new(path: Vec<Skill>, max_order: Vec<Skill>) -> Self {
+        Self { path, max_order }
+    }
+
+    /// e.g. "Q>E>W"
+    pub fn max_order_text(&self) -> String {
+        join_skills(&self.max_order, ">")
+    }
+
+    /// e.g. "Q W E Q Q R Q E Q E R E E W W R W W"
+    pub fn path_text(&self) -> String {
+        join_skills(&self.path, " ")
+    }
+}
+
+impl fmt::Display for SkillOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.max_order_text(), self.path_text())
+    }
+}
+
+fn join_skills(skills: &[Skill], separator: &str) -> String {
+    skills
+        .iter()
+        .map(Skill::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
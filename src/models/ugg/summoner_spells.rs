@@ -1,16 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::ugg::build_stats::BuildStats;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SummonerSpells {
     pub spell1_id: isize,
     pub spell2_id: isize,
+    pub stats: BuildStats,
 }
 
 impl SummonerSpells {
-    pub fn new(first: isize, second: isize) -> Self {
+    pub fn new(first: isize, second: isize, stats: BuildStats) -> Self {
         Self {
             spell1_id: first,
             spell2_id: second,
+            stats,
         }
     }
 }
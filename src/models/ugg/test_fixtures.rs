@@ -0,0 +1,101 @@
+//! Trimmed U.GG responses and DDragon data shared by the parsing tests. The JSON files
+//! keep U.GG's layout: region -> rank tier -> role -> `[overview, last updated]`.
+
+use reqwest::Client;
+
+use crate::models::ddragon_runes_reforged::{Rune, RuneData, RunesData, Slot};
+use crate::models::ugg::rank_tier::RankTier;
+use crate::models::ugg::region::Region;
+use crate::models::ugg::ugg_client::UggClient;
+use crate::models::ugg::ugg_overview::UggOverviewResponse;
+
+pub const OVERVIEW: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/ugg/overview.json"
+));
+
+pub const AP_OVERVIEW: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/ugg/ap_overview.json"
+));
+
+pub const MATCHUP_OVERVIEW: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/ugg/matchup_overview.json"
+));
+
+/// A client for World Platinum+, the region and rank tier the fixtures have data for.
+pub fn ugg_client() -> UggClient {
+    UggClient {
+        client: Client::new(),
+        patch_version: "14_1".to_owned(),
+        api_version: "1.5".to_owned(),
+        overview_version: "1.5.0".to_owned(),
+        base_url: "https://stats2.u.gg/lol/1.5".to_owned(),
+        region: Region::World,
+        rank_tier: RankTier::PlatinumPlus,
+    }
+}
+
+/// The role data for `role` in World Platinum+ from one of the overview fixtures.
+pub fn role_data(overview: &str, role: &str) -> serde_json::Value {
+    let mut response: UggOverviewResponse = serde_json::from_str(overview).unwrap();
+
+    response
+        .remove("12")
+        .and_then(|mut rank_tiers| rank_tiers.remove("10"))
+        .and_then(|mut roles| roles.remove(role))
+        .unwrap()
+}
+
+/// The Precision and Domination trees, enough to validate the fixture rune pages.
+pub fn runes_data() -> RunesData {
+    let tree = |id, name: &str, slots: &[&[isize]]| RuneData {
+        id,
+        key: name.to_owned(),
+        icon: String::new(),
+        name: name.to_owned(),
+        slots: slots
+            .iter()
+            .map(|runes| Slot {
+                runes: runes.iter().map(|&id| rune(id)).collect(),
+            })
+            .collect(),
+    };
+
+    RunesData {
+        runes_data: vec![
+            tree(
+                8000,
+                "Precision",
+                &[
+                    &[8005, 8008, 8021, 8010],
+                    &[9101, 9111, 8009],
+                    &[9104, 9105, 9103],
+                    &[8014, 8017, 8299],
+                ],
+            ),
+            tree(
+                8100,
+                "Domination",
+                &[
+                    &[8112, 8128, 9923],
+                    &[8126, 8139, 8143],
+                    &[8136, 8120, 8138],
+                    &[8135, 8105, 8106],
+                ],
+            ),
+        ],
+    }
+}
+
+fn rune(id: isize) -> Rune {
+    Rune {
+        id,
+        key: id.to_string(),
+        icon: String::new(),
+        name: id.to_string(),
+        short_desc: String::new(),
+        long_desc: String::new(),
+    }
+}
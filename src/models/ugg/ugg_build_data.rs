@@ -1,26 +1,43 @@
-use std::fmt::Write;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write as StdIOWrite;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use app_error::{bail, AppError, Result};
-use futures::{stream, StreamExt};
-use lcu_driver::endpoints::perks::PerksPage;
+use app_error::{bail, AppError, AppErrorExt, Result};
+use futures::{future, stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
+use crate::config::Config;
 use crate::models::ddragon_champions::Champion;
 use crate::models::ddragon_updater::DDragonUpdater;
 use crate::models::file_info::FileInfo;
 use crate::models::league_item_set::LeagueItemSet;
+use crate::models::localization::Localization;
 use crate::models::ugg::build_data::BuildData;
+use crate::models::ugg::cache_file::CacheFile;
 use crate::models::ugg::position::Position;
-use crate::models::ugg::summoner_spells::SummonerSpells;
+use crate::models::ugg::queue::Queue;
+use crate::models::ugg::rank_tier::RankTier;
+use crate::models::ugg::region::Region;
+use crate::models::ugg::resolved_build::{BuildFallback, ResolvedBuild};
 use crate::models::ugg::ugg_client::UggClient;
+use crate::util::normalize_name;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UggBuildData {
     pub patch_version: String,
-    pub builds: Vec<(Champion, Vec<BuildData>)>,
+    // The U.GG API and overview schema versions the builds were downloaded with, empty
+    // for caches written before they were recorded
+    #[serde(default)]
+    pub api_version: String,
+    #[serde(default)]
+    pub overview_version: String,
+    pub region: Region,
+    pub rank_tier: RankTier,
+    pub builds: Vec<(Champion, BTreeMap<Queue, Vec<BuildData>>)>,
 }
 
 impl UggBuildData {
@@ -31,7 +48,7 @@ impl UggBuildData {
         let required_version = ddragon.version.replace('.', "_");
 
         if !required_version.starts_with(&ugg_client.patch_version) {
-            println!(
+            warn!(
                 "Ugg data is not up to date with latest patch version. (Ugg: {}) (League: {})",
                 ugg_client.patch_version, ddragon.version
             );
@@ -39,38 +56,75 @@ impl UggBuildData {
 
         let champion_data = ddragon.download_latest_champions().await?;
         let runes_data = ddragon.download_latest_runes().await?;
+        let items_data = ddragon.download_latest_items().await?;
 
         let mut builds = Vec::with_capacity(champion_data.champion_list.len());
 
         let mut download_job = stream::iter(champion_data.champion_list)
-            .map(|champion| async {
-                let build_data = ugg_client.get_champion_data(&champion, &runes_data).await;
+            .map(|champion| {
+                let span = info_span!("download_champion", champion = %champion.name);
 
-                (champion, build_data)
-            })
-            .buffer_unordered(1 << 8); // 256
+                async {
+                    let champion_ref = &champion;
+                    let runes_data = &runes_data;
+
+                    let queue_jobs = Queue::iter().map(|queue| async move {
+                        let build_data = ugg_client
+                            .get_champion_data(champion_ref, queue, runes_data)
+                            .await;
 
-        while let Some((champion, build_data)) = download_job.next().await {
-            match build_data {
-                Ok(build_data) => {
-                    let mut curr_builds = Vec::with_capacity(5);
+                        (queue, build_data)
+                    });
 
-                    for build in build_data {
-                        match build {
-                            Ok(build_data) => {
-                                curr_builds.push(build_data);
+                    let build_data = future::join_all(queue_jobs).await;
+
+                    (champion, build_data)
+                }
+                .instrument(span)
+            })
+            .buffer_unordered(1 << 6); // 64
+
+        while let Some((champion, queue_build_data)) = download_job.next().await {
+            let mut queue_builds = BTreeMap::new();
+
+            for (queue, build_data) in queue_build_data {
+                match build_data {
+                    Ok(build_data) => {
+                        let mut curr_builds = Vec::with_capacity(5);
+
+                        for build in build_data {
+                            match build {
+                                Ok(mut build_data) => {
+                                    let removed =
+                                        build_data.retain_purchasable_items(&items_data, queue);
+
+                                    if !removed.is_empty() {
+                                        debug!(
+                                            "Removed unavailable items from {} {} ({}): {:?}",
+                                            champion.name, build_data.position, queue, removed
+                                        );
+                                    }
+
+                                    curr_builds.push(build_data);
+                                }
+                                Err(e) => warn!("{}", e),
                             }
-                            Err(e) => eprintln!("{}", e),
                         }
-                    }
 
-                    curr_builds.sort();
-                    builds.push((champion, curr_builds));
+                        curr_builds.sort();
+                        queue_builds.insert(queue, curr_builds);
+                    }
+                    Err(e) => warn!(
+                        "Failed to download {} build data for {} due to: {}",
+                        queue, champion.name, e
+                    ),
                 }
-                Err(e) => eprintln!(
-                    "Failed to download build data for {} due to: {}",
-                    champion.name, e
-                ),
+            }
+
+            if queue_builds.is_empty() {
+                error!("No build data found for {}", champion.name);
+            } else {
+                builds.push((champion, queue_builds));
             }
         }
 
@@ -78,55 +132,162 @@ impl UggBuildData {
 
         let ugg_build_data = UggBuildData {
             patch_version: ugg_client.patch_version.clone(),
+            api_version: ugg_client.api_version.clone(),
+            overview_version: ugg_client.overview_version.clone(),
+            region: ugg_client.region,
+            rank_tier: ugg_client.rank_tier,
             builds,
         };
 
         Ok(ugg_build_data)
     }
 
-    fn json_file_path(patch_version: &str) -> PathBuf {
-        Path::new(&format!("./ugg-builds-{}.json.sz", patch_version)).to_path_buf()
+    fn json_file_path(
+        data_dir: &Path,
+        patch_version: &str,
+        region: Region,
+        rank_tier: RankTier,
+    ) -> PathBuf {
+        data_dir.join(CacheFile::file_name(patch_version, region, rank_tier))
     }
 
-    async fn load_current_file_info(ddragon: &DDragonUpdater) -> Result<FileInfo> {
-        let file_info = FileInfo::read(Self::json_file_path(&ddragon.version)).await?;
+    async fn read_json(path: &Path, max_age: Option<Duration>) -> Result<Self> {
+        let file = FileInfo::read(path).await?;
+
+        // Enforces our data is not older than the configured cache age
+        if let Some(max_age) = max_age {
+            if file.metadata.modified()?.elapsed()? >= max_age {
+                bail!(
+                    "UGG Data is more than {} hours old.",
+                    max_age.as_secs() / 3600
+                );
+            }
+        }
+
+        let mut reader = snap::read::FrameDecoder::new(&*file.data);
+
+        let ugg_data = serde_json::from_reader(&mut reader)
+            .context("Existing UGG data is in an outdated format")?;
+
+        Ok(ugg_data)
+    }
 
-        // Enforces our data is at most 2 days old
-        if file_info.metadata.modified()?.elapsed()?.as_secs() >= (2 * 86400) {
-            bail!("UGG Data is more than 2 days old.");
+    /// Loads the cached data for the current patch and the configured region/rank tier.
+    /// If `max_age` is given, older data is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no cache file, it is too old, it can't be parsed or it
+    /// was built for a different region/rank tier.
+    pub async fn load_cached(
+        ddragon: &DDragonUpdater,
+        config: &Config,
+        max_age: Option<Duration>,
+    ) -> Result<Self> {
+        let path = Self::json_file_path(
+            &config.data_dir,
+            &ddragon.version,
+            config.region,
+            config.rank_tier,
+        );
+
+        let ugg_data = Self::read_json(&path, max_age).await?;
+
+        if ugg_data.region != config.region || ugg_data.rank_tier != config.rank_tier {
+            bail!(
+                "Existing UGG data is for {} {}, not {} {}",
+                ugg_data.region,
+                ugg_data.rank_tier,
+                config.region,
+                config.rank_tier
+            );
         }
 
-        Ok(file_info)
+        Ok(ugg_data)
     }
 
-    pub async fn load(ddragon: &DDragonUpdater) -> Result<Self> {
-        match Self::load_current_file_info(ddragon).await {
-            Ok(file) => {
-                let mut reader = snap::read::FrameDecoder::new(&*file.data);
+    /// Downloads the latest data from U.GG and saves it to the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if U.GG or DDragon can't be reached or the cache can't be written.
+    /// Champions that fail to download are logged and skipped rather than failing the update.
+    pub async fn update(ddragon: &DDragonUpdater, config: &Config) -> Result<Self> {
+        info!("Loading data from UGG...");
+
+        let ugg_client = UggClient::new(config).await?;
 
-                println!("Loading existing data...");
+        let data = Self::get_ugg_build_data(ddragon, &ugg_client).await?;
 
-                let ugg_data = serde_json::from_reader(&mut reader)?;
+        data.save_to_json(&config.data_dir, &ddragon.version)
+            .await?;
+
+        Ok(data)
+    }
+
+    /// Loads the cached data if it is recent enough, otherwise downloads it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`UggBuildData::update`].
+    pub async fn load(ddragon: &DDragonUpdater, config: &Config) -> Result<Self> {
+        match Self::load_cached(ddragon, config, Some(config.cache_max_age())).await {
+            Ok(ugg_data) => {
+                info!("Loaded existing data.");
 
                 Ok(ugg_data)
             }
             Err(e) => {
-                eprintln!("{e}");
+                info!("{e}");
+
+                Self::update(ddragon, config).await
+            }
+        }
+    }
 
-                println!("Loading data from UGG...");
+    /// Loads the newest cache file on disk without checking it against the live DDragon
+    /// version, preferring one for the configured region/rank tier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no cache files in the data directory or the newest
+    /// one can't be parsed.
+    pub async fn load_offline(config: &Config) -> Result<Self> {
+        let cache_files = CacheFile::list(&config.data_dir)?;
+
+        let cache_file = cache_files
+            .iter()
+            .find(|c| c.region == config.region && c.rank_tier == config.rank_tier)
+            .or_else(|| cache_files.first())
+            .context("No cached build data found to use offline")?;
+
+        if cache_file.region != config.region || cache_file.rank_tier != config.rank_tier {
+            warn!(
+                "No cached build data for {} {}, using {} {} instead",
+                config.region, config.rank_tier, cache_file.region, cache_file.rank_tier
+            );
+        }
 
-                let ugg_client = UggClient::new().await?;
+        let ugg_data = Self::read_json(&cache_file.path, None).await?;
 
-                let data = Self::get_ugg_build_data(ddragon, &ugg_client).await?;
+        let age_hours = cache_file
+            .modified
+            .elapsed()
+            .map(|age| age.as_secs() / 3600)
+            .unwrap_or(0);
 
-                data.save_to_json(&ddragon.version).await?;
+        warn!(
+            "Running offline with build data for patch {} from {} hours ago, it may be stale.",
+            cache_file.patch_version, age_hours
+        );
 
-                Ok(data)
-            }
-        }
+        Ok(ugg_data)
     }
 
-    pub async fn save_to_json(&self, patch_version: &str) -> Result<()> {
+    /// # Errors
+    ///
+    /// Returns an error if the data can't be serialized, compressed or written.
+    pub async fn save_to_json(&self, data_dir: &Path, patch_version: &str) -> Result<()> {
         let data = serde_json::to_string(&self)?;
 
         let mut compressed_writer = snap::write::FrameEncoder::new(Vec::with_capacity(data.len()));
@@ -137,11 +298,20 @@ impl UggBuildData {
             .into_inner()
             .map_err(|e| AppError::new(e.to_string()))?;
 
-        tokio::fs::write(Self::json_file_path(patch_version), compressed_data).await?;
+        tokio::fs::write(
+            Self::json_file_path(data_dir, patch_version, self.region, self.rank_tier),
+            compressed_data,
+        )
+        .await?;
 
         Ok(())
     }
 
+    /// Recursively deletes every `LH_*.json` item set in `builds_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory can't be read or a file can't be removed.
     pub fn delete_old_item_builds(builds_path: &Path) -> Result<()> {
         for entry in fs::read_dir(builds_path)? {
             let entry = entry?;
@@ -155,7 +325,7 @@ impl UggBuildData {
             {
                 let path = entry.path();
 
-                println!("Deleting: {}", path.display());
+                debug!("Deleting: {}", path.display());
 
                 fs::remove_file(path)?;
             }
@@ -164,80 +334,155 @@ impl UggBuildData {
         Ok(())
     }
 
-    pub fn save_item_builds(&self, builds_path: &Path) -> Result<()> {
-        for (champion, builds) in &self.builds {
-            for build_data in builds {
-                // Clone as don't want to modify the original data
-                let mut build_data = build_data.clone();
+    /// Writes every build as an item set into `builds_path`, the League `Config/Champions` folder.
+    /// Titles use the champion names from `localization`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an item set can't be serialized or written.
+    pub fn save_item_builds(
+        &self,
+        builds_path: &Path,
+        page_prefix: &str,
+        localization: &Localization,
+    ) -> Result<()> {
+        let mut saved_builds = 0;
+
+        for (champion, queue_builds) in &self.builds {
+            for (queue, builds) in queue_builds {
+                for build_data in builds {
+                    let build_file_path = builds_path.join(&champion.id).join("Recommended");
+
+                    if !build_file_path.exists() {
+                        fs::create_dir_all(&build_file_path)?;
+                    }
 
-                if let Some(starting_build) = build_data.item_sets.get_mut(0) {
-                    write!(
-                        starting_build.name,
-                        " [Skill Order: {}]",
-                        build_data.skill_order
-                    )?;
+                    let league_item_set = LeagueItemSet::from_build_data(
+                        build_data,
+                        champion,
+                        *queue,
+                        page_prefix,
+                        localization,
+                    );
+
+                    let league_item_set_json = serde_json::to_vec_pretty(&league_item_set)?;
+
+                    let mut build_file = fs::OpenOptions::new()
+                        .write(true)
+                        .create(true)
+                        .truncate(true)
+                        .open(build_file_path.join(format!(
+                            "LH_{}-{}-{}-{}.json",
+                            &champion.id, queue, &build_data.position, self.patch_version
+                        )))?;
+
+                    build_file.write_all(&league_item_set_json)?;
+
+                    debug!(
+                        "Saved build for: {} {} ({}).",
+                        champion.name, build_data.position, queue
+                    );
+
+                    saved_builds += 1;
                 }
+            }
+        }
 
-                let build_file_path = builds_path.join(&champion.id).join("Recommended");
-
-                if !build_file_path.exists() {
-                    fs::create_dir_all(&build_file_path)?;
-                }
+        info!(
+            "Saved {} item sets to: {}",
+            saved_builds,
+            builds_path.display()
+        );
 
-                let league_item_set = LeagueItemSet::from_build_data(&mut build_data, champion);
+        Ok(())
+    }
 
-                let league_item_set_json = serde_json::to_vec_pretty(&league_item_set)?;
+    pub fn most_played_position(&self, champion_key: isize, queue: Queue) -> Option<Position> {
+        self.find_champion_builds(champion_key)
+            .and_then(|(_, queue_builds)| queue_builds.get(&queue))
+            .and_then(|builds| Self::most_played_build(builds))
+            .map(|b| b.position)
+    }
 
-                let mut build_file = fs::OpenOptions::new()
-                    .write(true)
-                    .create(true)
-                    .truncate(true)
-                    .open(build_file_path.join(format!(
-                        "LH_{}-{}-{}.json",
-                        &champion.id, &build_data.position, self.patch_version
-                    )))?;
+    /// Finds the build for the requested position, falling back to the champion's
+    /// most played role in this queue, then to the same role in a queue played on the
+    /// same map and finally to any role from such a queue.
+    pub fn resolve_build(
+        &self,
+        champion_key: isize,
+        queue: Queue,
+        position: &Position,
+    ) -> Option<ResolvedBuild<'_>> {
+        let (champion, queue_builds) = self.find_champion_builds(champion_key)?;
+
+        let resolved = |queue, build_data, fallback| ResolvedBuild {
+            champion,
+            queue,
+            requested_position: *position,
+            build_data,
+            fallback,
+        };
 
-                build_file.write_all(&league_item_set_json)?;
+        if let Some(builds) = queue_builds.get(&queue) {
+            if let Some(build_data) = builds.iter().find(|b| b.position == *position) {
+                return Some(resolved(queue, build_data, BuildFallback::Exact));
+            }
 
-                println!(
-                    "Saved build for: {} {}.",
-                    champion.name, build_data.position
-                );
+            if let Some(build_data) = Self::most_played_build(builds) {
+                return Some(resolved(queue, build_data, BuildFallback::MostPopularRole));
             }
         }
 
-        Ok(())
-    }
+        // An ARAM or Arena build is no use on Summoner's Rift and vice versa
+        let compatible_builds = || {
+            queue_builds
+                .iter()
+                .filter(|(q, _)| q.is_compatible_with(queue))
+        };
 
-    pub fn get_perks_page(&self, champion_key: isize, position: &Position) -> Option<PerksPage> {
-        self.builds
-            .iter()
-            .find(|(champion, _)| champion.key == champion_key)
-            .and_then(|(champion, build_data)| {
-                let build_data = build_data.iter().find(|b| b.position == *position);
-
-                build_data.map(|b| PerksPage {
-                    name: format!("[LH] {} {}", champion.name, b.position),
-                    primary_style_id: b.rune_page.primary_tree,
-                    selected_perk_ids: b.rune_page.runes.clone(),
-                    sub_style_id: b.rune_page.secondary_tree,
-                    ..PerksPage::default()
+        // Prefer the same position from another queue before any other role
+        compatible_builds()
+            .find_map(|(q, builds)| {
+                builds
+                    .iter()
+                    .find(|b| b.position == *position)
+                    .map(|b| resolved(*q, b, BuildFallback::OtherQueue))
+            })
+            .or_else(|| {
+                compatible_builds().find_map(|(q, builds)| {
+                    Self::most_played_build(builds)
+                        .map(|b| resolved(*q, b, BuildFallback::AnyAvailable))
                 })
             })
     }
 
-    pub fn get_summoner_spells(
+    /// Finds a champion by name or id, ignoring case, spaces and punctuation.
+    pub fn find_champion_by_name(
+        &self,
+        name: &str,
+    ) -> Option<&(Champion, BTreeMap<Queue, Vec<BuildData>>)> {
+        let name = normalize_name(name);
+
+        self.builds.iter().find(|(champion, _)| {
+            normalize_name(&champion.name) == name || normalize_name(&champion.id) == name
+        })
+    }
+
+    pub fn find_champion(&self, champion_key: isize) -> Option<&Champion> {
+        self.find_champion_builds(champion_key)
+            .map(|(champion, _)| champion)
+    }
+
+    fn find_champion_builds(
         &self,
         champion_key: isize,
-        position: &Position,
-    ) -> Option<&SummonerSpells> {
+    ) -> Option<&(Champion, BTreeMap<Queue, Vec<BuildData>>)> {
         self.builds
             .iter()
             .find(|(champion, _)| champion.key == champion_key)
-            .and_then(|(_, build_data)| {
-                let build_data = build_data.iter().find(|b| b.position == *position);
+    }
 
-                build_data.map(|b| &b.summoner_spells)
-            })
+    fn most_played_build(builds: &[BuildData]) -> Option<&BuildData> {
+        builds.iter().max_by_key(|b| b.rune_page.stats.games)
     }
 }
@@ -1,30 +1,62 @@
+use std::collections::HashMap;
+
 use app_error::{bail, AppError, AppErrorExt, Result};
+use float_ord::FloatOrd;
+use futures::future;
 use regex::Regex;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, USER_AGENT};
 use reqwest::Client;
+use strum::IntoEnumIterator;
+use tracing::{debug, warn};
 
+use crate::config::Config;
 use crate::endpoints::ugg::UggEndpoint;
 use crate::models::ddragon_champions::Champion;
 use crate::models::ddragon_runes_reforged::RunesData;
 use crate::models::ugg::build_data::BuildData;
+use crate::models::ugg::build_variant::BuildVariant;
+use crate::models::ugg::matchup_build::MatchupBuild;
 use crate::models::ugg::position::Position;
+use crate::models::ugg::queue::Queue;
+use crate::models::ugg::rank_tier::RankTier;
+use crate::models::ugg::region::Region;
+use crate::models::ugg::rune_page::{RunePage, RunePageVariant};
+use crate::models::ugg::summoner_spells::SummonerSpells;
+use crate::models::ugg::ugg_matchup_data::UggMatchupData;
+use crate::models::ugg::ugg_overview::UggOverviewResponse;
 use crate::models::ugg::ugg_role_data::UggRoleData;
 
-const OVERVIEW_WORLD: &str = "12";
-const OVERVIEW_PLAT_PLUS: &str = "10";
-
+// Used when the versions can't be found on the U.GG home page
 const UGGAPI_VERSION: &str = "1.5";
 const UGGOVERVIEW_VERSION: &str = "1.5.0";
 
+// The overview schema major version `UggOverview` understands
+const SUPPORTED_OVERVIEW_MAJOR_VERSION: &str = "1";
+
+const VERSIONS_SSR_KEY: &str =
+    "https://static.bigbrain.gg/assets/lol/riot_patch_update/prod/versions.json";
+const API_VERSIONS_SSR_KEY: &str =
+    "https://static.bigbrain.gg/assets/lol/riot_patch_update/prod/ugg/ugg-api-versions.json";
+
 #[derive(Debug)]
 pub struct UggClient {
     pub client: Client,
     pub patch_version: String,
+    pub api_version: String,
+    pub overview_version: String,
     pub base_url: String,
+    pub region: Region,
+    pub rank_tier: RankTier,
 }
 
 impl UggClient {
-    pub async fn new() -> Result<Self> {
+    /// Creates a client for the latest patch U.GG has data for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the U.GG home page can't be fetched, the patch version can't
+    /// be found in it or U.GG uses an overview schema version we can't read.
+    pub async fn new(config: &Config) -> Result<Self> {
         let headers = HeaderMap::from_iter([
             (USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")),
             (ACCEPT, HeaderValue::from_static("text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8")),
@@ -51,7 +83,7 @@ impl UggClient {
         let data_json: serde_json::Value = serde_json::from_str(data_captures)?;
 
         let mut first_patch_version = data_json
-            .get("https://static.bigbrain.gg/assets/lol/riot_patch_update/prod/versions.json")
+            .get(VERSIONS_SSR_KEY)
             .and_then(|d| d.get("data"))
             .and_then(|d| d.as_array()?.first()?.as_str())
             .map(|d| d.split_terminator('.'))
@@ -66,62 +98,207 @@ impl UggClient {
 
         let patch_version = format!("{}_{}", major_version, minor_version);
 
-        let base_url = UggEndpoint::BaseUrl(UGGAPI_VERSION).url();
+        let (api_version, overview_version) = Self::api_versions(&data_json, &patch_version);
+
+        Self::check_overview_version(&overview_version)?;
+
+        debug!(
+            "Using U.GG API version {} with overview version {}",
+            api_version, overview_version
+        );
+
+        let base_url = UggEndpoint::BaseUrl(&api_version).url();
 
         Ok(UggClient {
             client,
             patch_version,
+            api_version,
+            overview_version,
             base_url,
+            region: config.region,
+            rank_tier: config.rank_tier,
         })
     }
 
+    // e.g. {"14_20": {"overview": "1.5.0", ...}}, the API version is the overview's
+    // major.minor
+    fn api_versions(data_json: &serde_json::Value, patch_version: &str) -> (String, String) {
+        let overview_version = data_json
+            .get(API_VERSIONS_SSR_KEY)
+            .and_then(|d| d.get("data"))
+            .and_then(|d| d.get(patch_version))
+            .and_then(|d| d.get("overview"))
+            .and_then(|d| d.as_str());
+
+        match overview_version {
+            Some(overview_version) => {
+                let api_version = overview_version
+                    .splitn(3, '.')
+                    .take(2)
+                    .collect::<Vec<_>>()
+                    .join(".");
+
+                (api_version, overview_version.to_owned())
+            }
+            None => {
+                warn!(
+                    "Couldn't find the U.GG API version, assuming {}",
+                    UGGOVERVIEW_VERSION
+                );
+
+                (UGGAPI_VERSION.to_owned(), UGGOVERVIEW_VERSION.to_owned())
+            }
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `overview_version` has a major version `UggOverview` can't read.
+    pub fn check_overview_version(overview_version: &str) -> Result<()> {
+        let major_version = overview_version.split('.').next().unwrap_or_default();
+
+        if major_version != SUPPORTED_OVERVIEW_MAJOR_VERSION {
+            bail!(
+                "U.GG now uses overview version {}, but only {}.x is supported. Please update league_helper, or use --offline to keep using the cached builds.",
+                overview_version,
+                SUPPORTED_OVERVIEW_MAJOR_VERSION
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Downloads the builds for every role of `champion` in `queue`, along with U.GG's
+    /// build variants to find the highest win rate rune page. Each role is parsed
+    /// separately so one bad role doesn't discard the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the response isn't JSON or it contains no roles.
     pub async fn get_champion_data(
         &self,
         champion: &Champion,
+        queue: Queue,
         runes_data: &RunesData,
     ) -> Result<Vec<Result<BuildData>>> {
-        let res = self
-            .call_endpoint(&UggEndpoint::ChampionData(
+        let (res, variant_responses) = future::join(
+            self.call_endpoint(&UggEndpoint::ChampionData(
                 &self.patch_version,
+                queue,
                 champion.key,
-                UGGOVERVIEW_VERSION,
+                &self.overview_version,
+            )),
+            self.get_variant_responses(champion.key, queue),
+        )
+        .await;
+
+        self.parse_champion_data(&res?, &variant_responses, &champion.name, queue, runes_data)
+    }
+
+    // Most champions only have a few build variants, so failed downloads are skipped
+    async fn get_variant_responses(&self, champion_key: isize, queue: Queue) -> Vec<String> {
+        let jobs = BuildVariant::iter().map(|variant| async move {
+            self.call_endpoint(&UggEndpoint::VariantData(
+                &self.patch_version,
+                variant,
+                queue,
+                champion_key,
+                &self.overview_version,
             ))
-            .await?;
+            .await
+            .inspect_err(|e| debug!("Skipping {} build variant: {}", variant, e))
+            .ok()
+        });
 
-        let data = json::parse(&res)?;
+        future::join_all(jobs).await.into_iter().flatten().collect()
+    }
+
+    fn parse_champion_data(
+        &self,
+        res: &str,
+        variant_responses: &[String],
+        champion_name: &str,
+        queue: Queue,
+        runes_data: &RunesData,
+    ) -> Result<Vec<Result<BuildData>>> {
+        let roles = self.parse_roles(res)?;
 
-        let data = &data[OVERVIEW_WORLD][OVERVIEW_PLAT_PLUS];
+        // Champions without a variant get an error page instead
+        let mut variant_roles = variant_responses
+            .iter()
+            .filter_map(|res| {
+                self.parse_roles(res)
+                    .inspect_err(|e| debug!("Skipping build variant: {}", e))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
 
         let format_error = |champion_name: &str, position: &Position, e| -> AppError {
-            AppError::new(format!("{} {} - {}", champion_name, position, e))
+            AppError::new(format!(
+                "{} {} ({}) - {}",
+                champion_name, position, queue, e
+            ))
         };
 
-        let build_data = data
-            .entries()
+        let build_data = roles
+            .into_iter()
             .map(|(k, v)| {
                 let key_int = k.parse::<isize>()?;
                 let position = Position::from(key_int);
-                let role_data = UggRoleData(v);
+                let role_data = UggRoleData::from_value(v)
+                    .map_err(|e| format_error(champion_name, &position, e))?;
 
                 let rune_page = role_data
-                    .get_rune_page(runes_data)
-                    .map_err(|e| format_error(&champion.name, &position, e))?;
-                let item_set = role_data
-                    .get_item_set()
-                    .map_err(|e| format_error(&champion.name, &position, e))?;
+                    .get_rune_page(RunePageVariant::Recommended, runes_data)
+                    .map_err(|e| format_error(champion_name, &position, e))?;
+
+                let variant_role_data = variant_roles
+                    .iter_mut()
+                    .filter_map(|roles| roles.remove(&k))
+                    .filter_map(|v| {
+                        UggRoleData::from_value(v)
+                            .inspect_err(|e| debug!("Skipping {} build variant: {}", position, e))
+                            .ok()
+                    })
+                    .collect::<Vec<_>>();
+
+                let variant_rune_pages = variant_role_data
+                    .iter()
+                    .filter_map(|variant| {
+                        variant
+                            .get_rune_page(RunePageVariant::HighestWinRate, runes_data)
+                            .inspect_err(|e| debug!("Skipping {} build variant: {}", position, e))
+                            .ok()
+                    })
+                    .collect::<Vec<_>>();
+
+                let alternative_rune_pages =
+                    Self::highest_win_rate_rune_page(&rune_page, variant_rune_pages)
+                        .into_iter()
+                        .collect();
+
+                let item_set = role_data.get_item_set();
                 let skill_order = role_data
                     .get_skill_order()
-                    .map_err(|e| format_error(&champion.name, &position, e))?;
-                let summoner_spells = role_data
-                    .get_summoner_spells()
-                    .map_err(|e| format_error(&champion.name, &position, e))?;
+                    .map_err(|e| format_error(champion_name, &position, e))?;
+                let summoner_spells = role_data.get_summoner_spells();
+
+                let alternative_summoner_spells = Self::alternative_summoner_spells(
+                    &summoner_spells,
+                    variant_role_data
+                        .iter()
+                        .map(UggRoleData::get_summoner_spells)
+                        .collect(),
+                );
 
                 Ok(BuildData {
                     position,
                     rune_page,
+                    alternative_rune_pages,
                     item_sets: item_set,
                     skill_order,
                     summoner_spells,
+                    alternative_summoner_spells,
                 })
             })
             .collect::<Vec<_>>();
@@ -133,13 +310,338 @@ impl UggClient {
         }
     }
 
+    // The variant page that wins the most after accounting for games played, if it does
+    // better than the recommended page
+    fn highest_win_rate_rune_page(
+        recommended: &RunePage,
+        variant_rune_pages: Vec<RunePage>,
+    ) -> Option<RunePage> {
+        variant_rune_pages
+            .into_iter()
+            .filter(|rune_page| rune_page.runes != recommended.runes)
+            .max_by_key(|rune_page| FloatOrd(rune_page.stats.win_rate_lower_bound))
+            .filter(|rune_page| {
+                rune_page.stats.win_rate_lower_bound > recommended.stats.win_rate_lower_bound
+            })
+    }
+
+    // The variants' spells that differ from the recommended ones, most played first, to
+    // stand in for a recommended spell that isn't allowed
+    fn alternative_summoner_spells(
+        recommended: &SummonerSpells,
+        mut variant_summoner_spells: Vec<SummonerSpells>,
+    ) -> Vec<SummonerSpells> {
+        let spell_ids = |s: &SummonerSpells| {
+            let mut ids = [s.spell1_id, s.spell2_id];
+            ids.sort_unstable();
+            ids
+        };
+
+        variant_summoner_spells.sort_by_key(|s| std::cmp::Reverse(s.stats.games));
+
+        let mut seen = vec![spell_ids(recommended)];
+
+        variant_summoner_spells.retain(|s| {
+            let ids = spell_ids(s);
+            let is_new = !seen.contains(&ids);
+            seen.push(ids);
+            is_new
+        });
+
+        variant_summoner_spells
+    }
+
+    /// Downloads the build for `champion_key` against `opponent_key` in `position`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the response isn't JSON or U.GG has no
+    /// data for this matchup.
+    pub async fn get_matchup_build(
+        &self,
+        champion_key: isize,
+        opponent_key: isize,
+        queue: Queue,
+        position: Position,
+        runes_data: &RunesData,
+    ) -> Result<MatchupBuild> {
+        let res = self
+            .call_endpoint(&UggEndpoint::MatchupData(
+                &self.patch_version,
+                queue,
+                champion_key,
+                opponent_key,
+                &self.overview_version,
+            ))
+            .await?;
+
+        self.parse_matchup_build(
+            &res,
+            champion_key,
+            opponent_key,
+            queue,
+            position,
+            runes_data,
+        )
+    }
+
+    fn parse_matchup_build(
+        &self,
+        res: &str,
+        champion_key: isize,
+        opponent_key: isize,
+        queue: Queue,
+        position: Position,
+        runes_data: &RunesData,
+    ) -> Result<MatchupBuild> {
+        let data = self
+            .parse_roles(res)?
+            .remove(&(position as isize).to_string())
+            .context(format!("No {} matchup data found.", position))?;
+
+        let matchup_data = UggMatchupData(UggRoleData::from_value(data)?);
+
+        Ok(MatchupBuild {
+            champion_key,
+            opponent_key,
+            queue,
+            position,
+            rune_page: matchup_data.get_rune_page(runes_data)?,
+            summoner_spells: matchup_data.get_summoner_spells(),
+        })
+    }
+
+    // The role data for our region and rank tier, keyed by U.GG's role id
+    fn parse_roles(&self, res: &str) -> Result<HashMap<String, serde_json::Value>> {
+        let mut data = serde_json::from_str::<UggOverviewResponse>(res)
+            .map_err(|e| AppError::new(format!("Unexpected U.GG overview response: {}", e)))?;
+
+        Ok(data
+            .remove(self.region.ugg_key())
+            .and_then(|mut rank_tiers| rank_tiers.remove(self.rank_tier.ugg_key()))
+            .unwrap_or_default())
+    }
+
     async fn call_endpoint(&self, endpoint: &UggEndpoint<'_>) -> Result<String> {
         let url = format!("{}{}", self.base_url, &endpoint.url());
 
-        println!("Making GET request to endpoint: {}", url);
+        debug!("Making GET request to endpoint: {}", url);
 
         let res = self.client.get(url).send().await?.text().await?;
 
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ugg::build_stats::BuildStats;
+    use crate::models::ugg::test_fixtures::{
+        runes_data, ugg_client, AP_OVERVIEW, MATCHUP_OVERVIEW, OVERVIEW,
+    };
+
+    #[test]
+    fn matchup_url() {
+        let endpoint = UggEndpoint::MatchupData("14_1", Queue::RankedSolo, 64, 104, "1.5.0");
+
+        assert_eq!(
+            endpoint.url(),
+            "/matchup_overview/14_1/ranked_solo_5x5/64/104/1.5.0.json"
+        );
+    }
+
+    #[test]
+    fn parses_matchup_build() {
+        let matchup_build = ugg_client()
+            .parse_matchup_build(
+                MATCHUP_OVERVIEW,
+                64,
+                104,
+                Queue::RankedSolo,
+                Position::Jungle,
+                &runes_data(),
+            )
+            .unwrap();
+
+        let rune_page = &matchup_build.rune_page;
+
+        assert_eq!(rune_page.variant, RunePageVariant::Matchup);
+        assert_eq!(rune_page.primary_tree, 8100);
+        assert_eq!(rune_page.secondary_tree, 8000);
+        // Sorted into slot order, followed by the stat shards
+        assert_eq!(
+            rune_page.runes,
+            vec![8112, 8139, 8138, 8105, 9111, 8014, 5008, 5002, 5001]
+        );
+        assert_eq!((rune_page.stats.games, rune_page.stats.wins), (820, 451));
+
+        let summoner_spells = &matchup_build.summoner_spells;
+
+        assert_eq!(
+            (summoner_spells.spell1_id, summoner_spells.spell2_id),
+            (4, 11)
+        );
+        assert_eq!(summoner_spells.stats.games, 840);
+    }
+
+    #[test]
+    fn matchup_without_role_data() {
+        let matchup_build = ugg_client().parse_matchup_build(
+            MATCHUP_OVERVIEW,
+            64,
+            104,
+            Queue::RankedSolo,
+            Position::Top,
+            &runes_data(),
+        );
+
+        assert!(matchup_build.is_err());
+    }
+
+    #[test]
+    fn matchup_for_other_rank_tier() {
+        let mut ugg_client = ugg_client();
+        ugg_client.rank_tier = RankTier::EmeraldPlus;
+
+        let matchup_build = ugg_client.parse_matchup_build(
+            MATCHUP_OVERVIEW,
+            64,
+            104,
+            Queue::RankedSolo,
+            Position::Jungle,
+            &runes_data(),
+        );
+
+        assert!(matchup_build.is_err());
+    }
+
+    #[test]
+    fn variant_url() {
+        let endpoint =
+            UggEndpoint::VariantData("14_1", BuildVariant::Ap, Queue::RankedSolo, 64, "1.5.0");
+
+        assert_eq!(
+            endpoint.url(),
+            "/ap-overview/14_1/ranked_solo_5x5/64/1.5.0.json"
+        );
+    }
+
+    #[test]
+    fn parses_highest_win_rate_rune_page() {
+        let build_data = ugg_client()
+            .parse_champion_data(
+                OVERVIEW,
+                &[AP_OVERVIEW.to_owned()],
+                "Lee Sin",
+                Queue::RankedSolo,
+                &runes_data(),
+            )
+            .unwrap();
+
+        let build_data = build_data.into_iter().next().unwrap().unwrap();
+
+        assert_eq!(build_data.position, Position::Jungle);
+        assert_eq!(build_data.alternative_rune_pages.len(), 1);
+
+        let rune_page = &build_data.alternative_rune_pages[0];
+
+        assert_eq!(rune_page.variant, RunePageVariant::HighestWinRate);
+        assert_eq!(rune_page.primary_tree, 8100);
+        assert_eq!(rune_page.secondary_tree, 8000);
+        assert_eq!(
+            rune_page.runes,
+            vec![8112, 8143, 8138, 8106, 9111, 8299, 5008, 5008, 5001]
+        );
+        assert_eq!((rune_page.stats.games, rune_page.stats.wins), (610, 380));
+
+        let alternative_spells = &build_data.alternative_summoner_spells;
+
+        assert_eq!(alternative_spells.len(), 1);
+        assert_eq!(
+            (
+                alternative_spells[0].spell1_id,
+                alternative_spells[0].spell2_id
+            ),
+            (4, 14)
+        );
+    }
+
+    #[test]
+    fn skips_invalid_build_variants() {
+        let build_data = ugg_client()
+            .parse_champion_data(
+                OVERVIEW,
+                &["<html>Not Found</html>".to_owned()],
+                "Lee Sin",
+                Queue::RankedSolo,
+                &runes_data(),
+            )
+            .unwrap();
+
+        let build_data = build_data.into_iter().next().unwrap().unwrap();
+
+        assert!(build_data.alternative_rune_pages.is_empty());
+        assert!(build_data.alternative_summoner_spells.is_empty());
+    }
+
+    #[test]
+    fn alternative_summoner_spells_skip_repeats() {
+        let spells = |spell1_id, spell2_id, games| {
+            SummonerSpells::new(
+                spell1_id,
+                spell2_id,
+                BuildStats::new(games, games / 2, games),
+            )
+        };
+
+        let alternatives = UggClient::alternative_summoner_spells(
+            &spells(4, 11, 5000),
+            vec![
+                spells(11, 4, 900),
+                spells(4, 14, 300),
+                spells(4, 6, 700),
+                spells(14, 4, 200),
+            ],
+        );
+
+        let spell_ids = alternatives
+            .iter()
+            .map(|s| (s.spell1_id, s.spell2_id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(spell_ids, vec![(4, 6), (4, 14)]);
+    }
+
+    #[test]
+    fn highest_win_rate_rune_page_must_beat_recommended() {
+        let rune_page = |runes: Vec<isize>, games, wins| RunePage {
+            runes,
+            primary_tree: 8000,
+            secondary_tree: 8100,
+            variant: RunePageVariant::HighestWinRate,
+            stats: BuildStats::new(games, wins, games),
+        };
+
+        let recommended = rune_page(vec![1, 2, 3], 5000, 2600);
+
+        let worse = rune_page(vec![4, 5, 6], 5000, 2500);
+        let too_few_games = rune_page(vec![4, 5, 6], 20, 15);
+        let same_runes = rune_page(vec![1, 2, 3], 5000, 3000);
+        let better = rune_page(vec![7, 8, 9], 5000, 2800);
+
+        assert!(UggClient::highest_win_rate_rune_page(
+            &recommended,
+            vec![worse.clone(), too_few_games.clone(), same_runes.clone()]
+        )
+        .is_none());
+
+        let highest = UggClient::highest_win_rate_rune_page(
+            &recommended,
+            vec![worse, too_few_games, same_runes, better],
+        )
+        .unwrap();
+
+        assert_eq!(highest.runes, vec![7, 8, 9]);
+    }
+}
@@ -0,0 +1,21 @@
+use app_error::Result;
+
+use crate::models::ddragon_runes_reforged::RunesData;
+use crate::models::ugg::rune_page::{RunePage, RunePageVariant};
+use crate::models::ugg::summoner_spells::SummonerSpells;
+use crate::models::ugg::ugg_role_data::UggRoleData;
+
+/// One role of a matchup overview. Runes and summoner spells use the same layout as
+/// the champion overview, so those are read with [`UggRoleData`].
+#[derive(Debug)]
+pub struct UggMatchupData(pub UggRoleData);
+
+impl UggMatchupData {
+    pub fn get_rune_page(&self, runes_data: &RunesData) -> Result<RunePage> {
+        self.0.get_rune_page(RunePageVariant::Matchup, runes_data)
+    }
+
+    pub fn get_summoner_spells(&self) -> SummonerSpells {
+        self.0.get_summoner_spells()
+    }
+}
@@ -0,0 +1,126 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use app_error::{bail, Result};
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::models::file_info::FileInfo;
+use crate::models::ugg::background_fetches::BackgroundFetches;
+use crate::models::ugg::lazy_ugg_client::LazyUggClient;
+use crate::models::ugg::matchup_build::MatchupBuild;
+use crate::models::ugg::position::Position;
+use crate::models::ugg::queue::Queue;
+
+const MATCHUPS_DIR_NAME: &str = "matchups";
+
+// Champion key, opponent key, queue, position
+type MatchupKey = (isize, isize, Queue, Position);
+
+/// Matchup builds fetched in the background the first time a matchup comes up in champ
+/// select, rather than downloaded in bulk. Results are kept in memory and cached on disk
+/// next to the builds.
+pub struct UggMatchups {
+    config: Config,
+    patch_version: String,
+    client: Arc<LazyUggClient>,
+    builds: BackgroundFetches<MatchupKey, MatchupBuild>,
+}
+
+impl UggMatchups {
+    /// `loaded` is notified whenever a matchup build has been fetched.
+    pub fn new(
+        config: &Config,
+        patch_version: &str,
+        client: Arc<LazyUggClient>,
+        loaded: Arc<Notify>,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            patch_version: patch_version.to_owned(),
+            client,
+            builds: BackgroundFetches::new(loaded),
+        }
+    }
+
+    /// The build for `champion_key` against `opponent_key`, if U.GG has one and it has
+    /// been fetched. Otherwise it is fetched in the background. Failures are logged and
+    /// remembered so the same matchup isn't requested again.
+    pub async fn get(
+        &self,
+        champion_key: isize,
+        opponent_key: isize,
+        queue: Queue,
+        position: Position,
+    ) -> Option<MatchupBuild> {
+        let key = (champion_key, opponent_key, queue, position);
+
+        let config = self.config.clone();
+        let path = self.file_path(key);
+        let client = self.client.clone();
+
+        self.builds
+            .get_or_fetch(key, || async move {
+                Self::load(&config, &path, &client, key)
+                    .await
+                    .inspect_err(|e| warn!("No matchup build available: {}", e))
+                    .ok()
+            })
+            .await
+    }
+
+    async fn load(
+        config: &Config,
+        path: &Path,
+        client: &LazyUggClient,
+        (champion_key, opponent_key, queue, position): MatchupKey,
+    ) -> Result<MatchupBuild> {
+        match FileInfo::read_json(path, config.cache_max_age()).await {
+            Ok(matchup_build) => {
+                debug!("Loaded cached matchup build from {}", path.display());
+
+                return Ok(matchup_build);
+            }
+            Err(e) => debug!("No cached matchup build: {}", e),
+        }
+
+        if config.offline {
+            bail!("matchup builds aren't cached and we are offline");
+        }
+
+        info!("Downloading matchup build...");
+
+        let client = client.get().await?;
+        let (ugg_client, runes_data) = client.as_ref();
+
+        let matchup_build = ugg_client
+            .get_matchup_build(champion_key, opponent_key, queue, position, runes_data)
+            .await?;
+
+        if let Err(e) = FileInfo::write_json(path, &matchup_build).await {
+            warn!("Failed to cache matchup build: {}", e);
+        }
+
+        Ok(matchup_build)
+    }
+
+    fn file_path(&self, (champion_key, opponent_key, queue, position): MatchupKey) -> PathBuf {
+        self.config
+            .data_dir
+            .join(MATCHUPS_DIR_NAME)
+            .join(format!(
+                "{}-{}-{}",
+                self.patch_version,
+                self.config.region.ugg_key(),
+                self.config.rank_tier.ugg_key()
+            ))
+            .join(format!(
+                "{}-{}-{}-{}.json",
+                champion_key,
+                opponent_key,
+                queue.ugg_key(),
+                position as isize
+            ))
+    }
+}
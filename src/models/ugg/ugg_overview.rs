@@ -0,0 +1,135 @@
+//! Typed version of the U.GG overview data, schema version `1.5.0`.
+//!
+//! U.GG sends every record as a JSON array, so each struct here is read from a sequence
+//! by position. Errors name the field that failed, e.g. `runes: primary_tree: invalid
+//! type: string "8000", expected isize`, and arrays may grow new trailing fields.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::{self, IgnoredAny, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+use crate::models::ugg::skill_order::Skill;
+
+/// Region -> rank tier -> role -> role data, as returned by the overview endpoints.
+pub type UggOverviewResponse = HashMap<String, HashMap<String, HashMap<String, serde_json::Value>>>;
+
+// Declares a struct that deserializes from a JSON array, one field per element in order
+macro_rules! ugg_array {
+    ($(#[$meta:meta])* pub struct $name:ident { $($field:ident: $ty:ty,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct ArrayVisitor;
+
+                impl<'de> Visitor<'de> for ArrayVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        write!(f, "a U.GG {} array", stringify!($name))
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$name, A::Error> {
+                        $(
+                            let $field = seq
+                                .next_element::<$ty>()
+                                .map_err(|e| {
+                                    de::Error::custom(format!("{}: {}", stringify!($field), e))
+                                })?
+                                .ok_or_else(|| {
+                                    de::Error::custom(format!("{}: missing", stringify!($field)))
+                                })?;
+                        )*
+
+                        while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+                        Ok($name { $($field,)* })
+                    }
+                }
+
+                deserializer.deserialize_seq(ArrayVisitor)
+            }
+        }
+    };
+}
+
+ugg_array! {
+    /// Everything U.GG recommends for one champion in one role.
+    pub struct UggOverview {
+        runes: UggRunes,
+        summoner_spells: UggSummonerSpells,
+        starting_items: UggItems,
+        core_items: UggItems,
+        skills: UggSkills,
+        item_options: Vec<Vec<UggItemOption>>,
+        role_stats: UggRoleStats,
+        unused: IgnoredAny,
+        stat_shards: UggStatShards,
+    }
+}
+
+ugg_array! {
+    pub struct UggRunes {
+        games: isize,
+        wins: isize,
+        primary_tree: isize,
+        secondary_tree: isize,
+        runes: Vec<isize>,
+    }
+}
+
+ugg_array! {
+    pub struct UggSummonerSpells {
+        games: isize,
+        wins: isize,
+        spells: (isize, isize),
+    }
+}
+
+ugg_array! {
+    pub struct UggItems {
+        wins: isize,
+        games: isize,
+        items: Vec<isize>,
+    }
+}
+
+ugg_array! {
+    pub struct UggSkills {
+        games: isize,
+        wins: isize,
+        path: Vec<Skill>,
+        // e.g. "QEW"
+        max_order: String,
+    }
+}
+
+ugg_array! {
+    pub struct UggItemOption {
+        item: isize,
+        wins: isize,
+        games: isize,
+    }
+}
+
+ugg_array! {
+    pub struct UggRoleStats {
+        wins: isize,
+        games: isize,
+    }
+}
+
+ugg_array! {
+    pub struct UggStatShards {
+        games: isize,
+        wins: isize,
+        // Shard ids are sent as strings
+        shards: Vec<String>,
+    }
+}
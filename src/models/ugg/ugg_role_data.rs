@@ -1,26 +1,58 @@
-use app_error::{bail, AppErrorExt, Result};
-use json::JsonValue;
+use app_error::{bail, AppError, AppErrorExt, Result};
 
 use crate::models::ddragon_runes_reforged::RunesData;
-use crate::models::ugg::item_set::ItemSet;
-use crate::models::ugg::rune_page::RunePage;
+use crate::models::ugg::build_stats::BuildStats;
+use crate::models::ugg::item_set::{ItemSet, ItemSetStats};
+use crate::models::ugg::rune_page::{RunePage, RunePageVariant};
+use crate::models::ugg::skill_order::{Skill, SkillOrder};
 use crate::models::ugg::summoner_spells::SummonerSpells;
-use crate::util::calc_win_rate;
+use crate::models::ugg::ugg_overview::UggOverview;
 
 #[derive(Debug)]
-pub struct UggRoleData<'a>(pub &'a JsonValue);
+pub struct UggRoleData {
+    pub overview: UggOverview,
+}
+
+impl UggRoleData {
+    /// Parses one role of an overview response, `[overview, last updated]`. Alternative
+    /// builds for the role are in their own overviews, see [`BuildVariant`].
+    ///
+    /// [`BuildVariant`]: crate::models::ugg::build_variant::BuildVariant
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the field that doesn't match the expected schema.
+    pub fn from_value(value: serde_json::Value) -> Result<Self> {
+        let serde_json::Value::Array(role_data) = value else {
+            bail!("Unexpected U.GG role data, expected an array");
+        };
+
+        let overview = role_data
+            .into_iter()
+            .next()
+            .context("Missing U.GG overview")
+            .and_then(|v| {
+                serde_json::from_value(v)
+                    .map_err(|e| AppError::new(format!("Unexpected U.GG overview: {}", e)))
+            })?;
 
-impl<'a> UggRoleData<'a> {
-    pub fn get_rune_page(&self, runes_data: &RunesData) -> Result<RunePage> {
-        let data = self.0;
+        Ok(Self { overview })
+    }
+
+    // Every game the champion played in this role, used for pick rates
+    fn role_games(&self) -> isize {
+        self.overview.role_stats.games
+    }
 
-        let primary_tree = data[0][0][2]
-            .as_isize()
-            .context("Failed to read runes primary tree")?;
+    pub fn get_rune_page(
+        &self,
+        variant: RunePageVariant,
+        runes_data: &RunesData,
+    ) -> Result<RunePage> {
+        let overview = &self.overview;
 
-        let secondary_tree = data[0][0][3]
-            .as_isize()
-            .context("Failed to read runes secondary tree")?;
+        let primary_tree = overview.runes.primary_tree;
+        let secondary_tree = overview.runes.secondary_tree;
 
         let valid_primary_tree_runes = runes_data
             .runes_data
@@ -36,10 +68,7 @@ impl<'a> UggRoleData<'a> {
             .map(|r| &r.slots)
             .context("Secondary tree rune key was not valid")?;
 
-        let mut runes = data[0][0][4]
-            .members()
-            .map(|v| v.as_isize().unwrap_or(0))
-            .collect::<Vec<_>>();
+        let mut runes = overview.runes.runes.clone();
 
         let mut i = 0;
 
@@ -71,14 +100,16 @@ impl<'a> UggRoleData<'a> {
             }
         }
 
-        let mut stat_shards = data[0][8][2]
-            .members()
-            .map(|v| {
-                v.as_str()
-                    .and_then(|v| v.parse::<isize>().ok())
-                    .unwrap_or(0)
+        let mut stat_shards = overview
+            .stat_shards
+            .shards
+            .iter()
+            .map(|shard| {
+                shard.parse::<isize>().map_err(|_| {
+                    AppError::new(format!("Unexpected U.GG stat shard id: {:?}", shard))
+                })
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>>>()?;
 
         runes.append(&mut stat_shards);
 
@@ -86,98 +117,196 @@ impl<'a> UggRoleData<'a> {
             bail!("Could not find a complete rune page");
         }
 
-        // Rune page win rate
-        let games_played = data[0][0][0]
-            .as_isize()
-            .context("Failed to read runes games played")?;
-
-        let games_won = data[0][0][1]
-            .as_isize()
-            .context("Failed to read runes games won")?;
-
-        let win_rate = calc_win_rate(games_won as f32, games_played as f32);
-
         Ok(RunePage {
             runes,
             primary_tree,
             secondary_tree,
-            win_rate,
+            variant,
+            stats: BuildStats::new(overview.runes.games, overview.runes.wins, self.role_games()),
         })
     }
 
-    pub fn get_item_set(&self) -> Result<Vec<ItemSet>> {
-        let data = self.0;
+    pub fn get_item_set(&self) -> Vec<ItemSet> {
+        let overview = &self.overview;
+        let role_games = self.role_games();
 
         let mut sets = Vec::new();
 
-        let mut add_with_win_rate = |name: &str, index: usize| -> Result<()> {
-            let games_won = data[0][index][0]
-                .as_f64()
-                .context(format!("Failed to read item set: {} for games_won", name))?;
-
-            let games_played = data[0][index][1].as_f64().context(format!(
-                "Failed to read item set: {} for games_played",
-                name
-            ))?;
+        for (name, items) in [
+            ("Starting Build", &overview.starting_items),
+            ("Core Build", &overview.core_items),
+        ] {
+            let stats = BuildStats::new(items.games, items.wins, role_games);
 
             sets.push(ItemSet {
                 name: format!(
                     "{} - {:.2}% win rate",
                     name,
-                    (games_played / games_won) * 100_f64
+                    stats.win_rate_lower_bound * 100.0
                 ),
-                items: data[0][index][2]
-                    .members()
-                    .map(|v| v.as_isize().unwrap_or(0))
-                    .collect(),
+                items: items.items.clone(),
+                stats: ItemSetStats::Set(stats),
             });
-
-            Ok(())
-        };
-
-        add_with_win_rate("Starting Build", 2)?;
-        add_with_win_rate("Core Build", 3)?;
+        }
 
         let set_names = ["Fourth", "Fifth", "Sixth"];
 
-        data[0][5].members().enumerate().for_each(|(i, v)| {
+        for (i, options) in overview.item_options.iter().enumerate() {
             sets.push(ItemSet {
                 name: format!(
                     "{} Item Options (ordered by games played)",
                     set_names.get(i).unwrap_or(&"Unknown")
                 ),
-                items: v.members().map(|v| v[0].as_isize().unwrap_or(0)).collect(),
+                items: options.iter().map(|o| o.item).collect(),
+                stats: ItemSetStats::PerItem(
+                    options
+                        .iter()
+                        .map(|o| BuildStats::new(o.games, o.wins, role_games))
+                        .collect(),
+                ),
             });
-        });
+        }
 
-        Ok(sets)
+        sets
     }
 
-    pub fn get_skill_order(&self) -> Result<String> {
-        let data = self.0;
+    pub fn get_skill_order(&self) -> Result<SkillOrder> {
+        let skills = &self.overview.skills;
 
-        let skill_order = data[0][4][3]
-            .as_str()
-            .context("Failed to read skill order")?
+        let max_order = skills
+            .max_order
             .chars()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(">");
+            .map(|c| {
+                c.to_string().parse::<Skill>().map_err(|_| {
+                    AppError::new(format!("Unexpected U.GG skill in max order: {:?}", c))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SkillOrder::new(skills.path.clone(), max_order))
+    }
+
+    pub fn get_summoner_spells(&self) -> SummonerSpells {
+        let summoner_spells = &self.overview.summoner_spells;
+
+        let (first, second) = summoner_spells.spells;
+
+        SummonerSpells::new(
+            first,
+            second,
+            BuildStats::new(
+                summoner_spells.games,
+                summoner_spells.wins,
+                self.role_games(),
+            ),
+        )
+    }
+}
 
-        Ok(skill_order)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ugg::test_fixtures::{role_data, runes_data, OVERVIEW};
+
+    fn jungle() -> UggRoleData {
+        UggRoleData::from_value(role_data(OVERVIEW, "1")).unwrap()
+    }
+
+    #[test]
+    fn reads_overview_before_last_updated() {
+        let role_data = jungle();
+
+        assert_eq!(role_data.overview.role_stats.games, 6100);
+        assert_eq!(role_data.overview.role_stats.wins, 3150);
+    }
+
+    #[test]
+    fn rejects_role_data_that_isnt_an_array() {
+        assert!(UggRoleData::from_value(serde_json::json!({})).is_err());
+        assert!(UggRoleData::from_value(serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn recommended_rune_page() {
+        let rune_page = jungle()
+            .get_rune_page(RunePageVariant::Recommended, &runes_data())
+            .unwrap();
+
+        assert_eq!(rune_page.variant, RunePageVariant::Recommended);
+        assert_eq!(rune_page.primary_tree, 8000);
+        assert_eq!(rune_page.secondary_tree, 8100);
+        assert_eq!(
+            rune_page.runes,
+            vec![8010, 9111, 9104, 8299, 8139, 8135, 5008, 5008, 5001]
+        );
+        assert_eq!((rune_page.stats.games, rune_page.stats.wins), (5120, 2688));
+    }
+
+    #[test]
+    fn summoner_spells() {
+        let summoner_spells = jungle().get_summoner_spells();
+
+        assert_eq!(
+            (summoner_spells.spell1_id, summoner_spells.spell2_id),
+            (4, 11)
+        );
+        assert_eq!(
+            (summoner_spells.stats.games, summoner_spells.stats.wins),
+            (6010, 3115)
+        );
+    }
+
+    #[test]
+    fn starting_and_core_builds() {
+        let item_sets = jungle().get_item_set();
+
+        let starting = &item_sets[0];
+
+        // Titles show the Wilson lower bound, not the raw 51.86%
+        assert_eq!(starting.name, "Starting Build - 47.94% win rate");
+        assert_eq!(starting.items, vec![1103, 2003]);
+
+        let ItemSetStats::Set(stats) = &starting.stats else {
+            panic!("Expected stats for the whole starting build");
+        };
+
+        assert_eq!((stats.games, stats.wins), (2950, 1530));
+
+        let core = &item_sets[1];
+
+        assert_eq!(core.name, "Core Build - 47.70% win rate");
+        assert_eq!(core.items, vec![6692, 3071, 3047]);
+
+        let ItemSetStats::Set(stats) = &core.stats else {
+            panic!("Expected stats for the whole core build");
+        };
+
+        assert_eq!((stats.games, stats.wins), (1310, 702));
     }
 
-    pub fn get_summoner_spells(&self) -> Result<SummonerSpells> {
-        let data = self.0;
+    #[test]
+    fn item_options() {
+        let item_sets = jungle().get_item_set();
+
+        assert_eq!(item_sets.len(), 5);
 
-        let first = data[0][1][2][0]
-            .as_isize()
-            .context("Failed to read first summoner spell")?;
+        let fourth = &item_sets[2];
+
+        assert_eq!(fourth.name, "Fourth Item Options (ordered by games played)");
+        assert_eq!(fourth.items, vec![3053, 6333]);
+
+        let ItemSetStats::PerItem(stats) = &fourth.stats else {
+            panic!("Expected stats per item option");
+        };
 
-        let second = data[0][1][2][1]
-            .as_isize()
-            .context("Failed to read second summoner spell")?;
+        assert_eq!(
+            stats.iter().map(|s| (s.games, s.wins)).collect::<Vec<_>>(),
+            vec![(400, 210), (300, 150)]
+        );
+        // Pick rates are out of every game in the role
+        assert_eq!(stats[0].pick_rate, 400.0 / 6100.0);
 
-        Ok(SummonerSpells::new(first, second))
+        assert_eq!(item_sets[3].items, vec![3026]);
+        assert_eq!(item_sets[4].items, vec![3156]);
     }
 }
@@ -0,0 +1,231 @@
+use lcu_driver::endpoints::gameflow::GameFlowPhase;
+
+#[derive(Clone, Copy, Debug, Default, strum::Display, Eq, PartialEq)]
+pub enum SessionState {
+    #[default]
+    Idle,
+    Lobby,
+    ChampSelect,
+    Loading,
+    InGame,
+    EndOfGame,
+    Reconnect,
+}
+
+impl From<&GameFlowPhase> for SessionState {
+    fn from(phase: &GameFlowPhase) -> Self {
+        match phase {
+            GameFlowPhase::Lobby
+            | GameFlowPhase::Matchmaking
+            | GameFlowPhase::CheckedIntoTournament
+            | GameFlowPhase::ReadyCheck => SessionState::Lobby,
+            GameFlowPhase::ChampSelect => SessionState::ChampSelect,
+            GameFlowPhase::GameStart => SessionState::Loading,
+            GameFlowPhase::InProgress => SessionState::InGame,
+            GameFlowPhase::WaitingForStats
+            | GameFlowPhase::PreEndOfGame
+            | GameFlowPhase::EndOfGame => SessionState::EndOfGame,
+            GameFlowPhase::Reconnect => SessionState::Reconnect,
+            _ => SessionState::Idle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Transition {
+    pub from: SessionState,
+    pub to: SessionState,
+}
+
+impl Transition {
+    /// Whether this is a step the client normally takes. Anything else means we missed
+    /// updates, e.g. the client or league_helper restarted part way through a game.
+    pub fn is_expected(&self) -> bool {
+        use SessionState::*;
+
+        matches!(
+            (self.from, self.to),
+            (_, Idle)
+                | (_, Lobby)
+                | (Idle | Lobby, ChampSelect)
+                | (ChampSelect, Loading)
+                | (Loading | Reconnect, InGame)
+                | (InGame, Reconnect | EndOfGame)
+                | (Loading | Reconnect, EndOfGame)
+        )
+    }
+}
+
+/// Tracks which part of the gameflow the client is in so each state's setup and
+/// teardown happens exactly once, however often the same phase is reported.
+#[derive(Debug, Default)]
+pub struct Session {
+    state: SessionState,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Moves to the state for `phase`, returning the transition if the state changed.
+    pub fn advance(&mut self, phase: &GameFlowPhase) -> Option<Transition> {
+        self.transition_to(SessionState::from(phase))
+    }
+
+    /// Returns to `Idle`, e.g. when the connection to the client has been lost.
+    pub fn reset(&mut self) -> Option<Transition> {
+        self.transition_to(SessionState::Idle)
+    }
+
+    fn transition_to(&mut self, to: SessionState) -> Option<Transition> {
+        if self.state == to {
+            return None;
+        }
+
+        let transition = Transition {
+            from: self.state,
+            to,
+        };
+
+        self.state = to;
+
+        Some(transition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Feeds recorded phases through a new session, collecting the transitions
+    fn replay(session: &mut Session, phases: &[GameFlowPhase]) -> Vec<Transition> {
+        phases.iter().filter_map(|p| session.advance(p)).collect()
+    }
+
+    fn transition(from: SessionState, to: SessionState) -> Transition {
+        Transition { from, to }
+    }
+
+    #[test]
+    fn normal_game() {
+        use SessionState::*;
+
+        let mut session = Session::new();
+
+        let transitions = replay(
+            &mut session,
+            &[
+                GameFlowPhase::Lobby,
+                GameFlowPhase::Matchmaking,
+                GameFlowPhase::ReadyCheck,
+                GameFlowPhase::ChampSelect,
+                GameFlowPhase::ChampSelect,
+                GameFlowPhase::GameStart,
+                GameFlowPhase::InProgress,
+                GameFlowPhase::WaitingForStats,
+                GameFlowPhase::PreEndOfGame,
+                GameFlowPhase::EndOfGame,
+                GameFlowPhase::Lobby,
+            ],
+        );
+
+        assert_eq!(
+            transitions,
+            vec![
+                transition(Idle, Lobby),
+                transition(Lobby, ChampSelect),
+                transition(ChampSelect, Loading),
+                transition(Loading, InGame),
+                transition(InGame, EndOfGame),
+                transition(EndOfGame, Lobby),
+            ]
+        );
+        assert!(transitions.iter().all(Transition::is_expected));
+        assert_eq!(session.state(), Lobby);
+    }
+
+    #[test]
+    fn repeated_phase_is_not_a_transition() {
+        let mut session = Session::new();
+
+        assert!(session.advance(&GameFlowPhase::ChampSelect).is_some());
+        assert_eq!(session.advance(&GameFlowPhase::ChampSelect), None);
+    }
+
+    #[test]
+    fn client_restart_during_champ_select() {
+        use SessionState::*;
+
+        let mut session = Session::new();
+
+        replay(
+            &mut session,
+            &[GameFlowPhase::Lobby, GameFlowPhase::ChampSelect],
+        );
+
+        // The connection is lost, then the new client reports champ select again
+        assert_eq!(session.reset(), Some(transition(ChampSelect, Idle)));
+        assert_eq!(session.reset(), None);
+
+        let transitions = replay(
+            &mut session,
+            &[GameFlowPhase::ChampSelect, GameFlowPhase::GameStart],
+        );
+
+        assert_eq!(
+            transitions,
+            vec![
+                transition(Idle, ChampSelect),
+                transition(ChampSelect, Loading)
+            ]
+        );
+        assert!(transitions.iter().all(Transition::is_expected));
+    }
+
+    #[test]
+    fn reconnect_to_game() {
+        use SessionState::*;
+
+        let mut session = Session::new();
+
+        let transitions = replay(
+            &mut session,
+            &[
+                GameFlowPhase::InProgress,
+                GameFlowPhase::Reconnect,
+                GameFlowPhase::InProgress,
+                GameFlowPhase::EndOfGame,
+            ],
+        );
+
+        assert_eq!(
+            transitions,
+            vec![
+                transition(Idle, InGame),
+                transition(InGame, Reconnect),
+                transition(Reconnect, InGame),
+                transition(InGame, EndOfGame),
+            ]
+        );
+
+        // Started part way through a game, which the client never does by itself
+        assert!(!transitions[0].is_expected());
+        assert!(transitions[1..].iter().all(Transition::is_expected));
+    }
+
+    #[test]
+    fn unexpected_transitions() {
+        use SessionState::*;
+
+        assert!(!transition(Lobby, InGame).is_expected());
+        assert!(!transition(ChampSelect, EndOfGame).is_expected());
+        assert!(!transition(EndOfGame, ChampSelect).is_expected());
+        assert!(transition(ChampSelect, Lobby).is_expected());
+        assert!(transition(InGame, Idle).is_expected());
+    }
+}
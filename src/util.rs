@@ -17,3 +17,11 @@ pub fn calc_win_rate(games_won: f32, games_played: f32) -> f32 {
             ))
         / (1.0 + confidence_inverse_norm_dist * confidence_inverse_norm_dist / games_played)
 }
+
+// Champion names compared ignoring case, spaces and punctuation, e.g. "Kai'Sa" and kaisa
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}